@@ -20,6 +20,8 @@ impl<R> LineStream<R> {
         }
     }
 
+    /// Next `num_lines` lines joined together, without the final newline. `None` once the input is
+    /// exhausted.
     pub fn next_line(&mut self) -> Option<&'_ [u8]>
     where
         R: BufRead,
@@ -31,7 +33,10 @@ impl<R> LineStream<R> {
         if self.buffer.is_empty() {
             None
         } else {
-            self.buffer.pop();
+            // Last line of the input may not be terminated by a newline
+            if self.buffer.last() == Some(&b'\n') {
+                self.buffer.pop();
+            }
             Some(&self.buffer)
         }
     }
@@ -65,3 +70,24 @@ where
             .map(|line| (self.line_to_input)(line))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::LineStream;
+
+    fn collect_lines(input: &[u8]) -> Vec<String> {
+        let mut lines = LineStream::new(input);
+        let mut collected = Vec::new();
+        while let Some(line) = lines.next_line() {
+            collected.push(String::from_utf8(line.to_vec()).unwrap());
+        }
+        collected
+    }
+
+    #[test]
+    fn last_line_without_newline() {
+        assert_eq!(vec!["ab", "cd"], collect_lines(b"ab\ncd\n"));
+        assert_eq!(vec!["ab", "cd"], collect_lines(b"ab\ncd"));
+        assert_eq!(vec!["ab", "", "c"], collect_lines(b"ab\n\nc"));
+    }
+}
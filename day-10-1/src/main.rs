@@ -215,11 +215,8 @@ use std::{
     io::{BufRead, BufReader},
 };
 
-use atoi::FromRadix10SignedChecked;
-
-use lines::InputIterator;
-
-mod lines;
+use common::InputIterator;
+use day_10_common::{ClockCircuit, Instruction, SignalStrength};
 
 fn main() {
     let input = BufReader::new(File::open("input.txt").expect("Can not open input file"));
@@ -228,259 +225,20 @@ fn main() {
 }
 
 fn sum_signal_strength(input: impl BufRead) -> i64 {
-    let interesting_cycles = [20, 60, 100, 140, 180, 220];
     let instructions = InputIterator::new(input, Instruction::from_line);
     let mut cc = ClockCircuit::new(instructions);
-    let mut sum_signal_strength = 0;
-    for cycle in 1..=220 {
-        if interesting_cycles.contains(&cycle) {
-            sum_signal_strength += cc.signal_strength();
-        }
-        cc.tick();
-    }
-    sum_signal_strength
-}
-
-struct ClockCircuit<I> {
-    instructions: I,
-    register: Option<i64>,
-    x: i64,
-    cycle: i64,
-}
-
-impl<I> ClockCircuit<I> {
-    fn new(instructions: I) -> Self {
-        Self {
-            x: 1,
-            cycle: 1,
-            register: None,
-            instructions,
-        }
-    }
-
-    fn tick(&mut self)
-    where
-        I: Iterator<Item = Instruction>,
-    {
-        if let Some(add_me) = self.register {
-            self.x += add_me;
-            self.register = None;
-        } else {
-            let inst = self.instructions.next().expect("yagni");
-            match inst {
-                Instruction::Noop => (),
-                Instruction::AddX(amount) => {
-                    self.register = Some(amount);
-                }
-            }
-        }
-        self.cycle += 1;
-    }
-
-    fn x(&self) -> i64 {
-        self.x
-    }
-
-    fn signal_strength(&self) -> i64 {
-        self.x() * self.cycle
-    }
-}
-
-enum Instruction {
-    Noop,
-    AddX(i64),
-}
-
-impl Instruction {
-    fn from_line(line: &[u8]) -> Self {
-        match &line[..4] {
-            b"noop" => Self::Noop,
-            b"addx" => {
-                let (amount, _) = i64::from_radix_10_signed_checked(&line[5..]);
-                Self::AddX(amount.expect("Invalid Addx syntax"))
-            }
-            _ => panic!("yagni"),
-        }
-    }
+    let mut signal_strength = SignalStrength::new();
+    cc.run(220, &mut signal_strength);
+    signal_strength.sum()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{lines::InputIterator, sum_signal_strength, ClockCircuit, Instruction};
-
-    #[test]
-    fn small_program() {
-        let input = "\
-            noop\n\
-            addx 3\n\
-            addx -5\n\
-        ";
-        let instructions = InputIterator::new(input.as_bytes(), Instruction::from_line);
-        let mut cc = ClockCircuit::new(instructions);
-
-        assert_eq!(1, cc.x());
-        cc.tick();
-        assert_eq!(1, cc.x());
-        cc.tick();
-        assert_eq!(1, cc.x());
-        cc.tick();
-        assert_eq!(4, cc.x());
-        cc.tick();
-        assert_eq!(4, cc.x());
-        cc.tick();
-        assert_eq!(-1, cc.x());
-    }
+    use crate::sum_signal_strength;
 
     #[test]
     fn larger_programm() {
-        let input = "\
-            addx 15\n\
-            addx -11\n\
-            addx 6\n\
-            addx -3\n\
-            addx 5\n\
-            addx -1\n\
-            addx -8\n\
-            addx 13\n\
-            addx 4\n\
-            noop\n\
-            addx -1\n\
-            addx 5\n\
-            addx -1\n\
-            addx 5\n\
-            addx -1\n\
-            addx 5\n\
-            addx -1\n\
-            addx 5\n\
-            addx -1\n\
-            addx -35\n\
-            addx 1\n\
-            addx 24\n\
-            addx -19\n\
-            addx 1\n\
-            addx 16\n\
-            addx -11\n\
-            noop\n\
-            noop\n\
-            addx 21\n\
-            addx -15\n\
-            noop\n\
-            noop\n\
-            addx -3\n\
-            addx 9\n\
-            addx 1\n\
-            addx -3\n\
-            addx 8\n\
-            addx 1\n\
-            addx 5\n\
-            noop\n\
-            noop\n\
-            noop\n\
-            noop\n\
-            noop\n\
-            addx -36\n\
-            noop\n\
-            addx 1\n\
-            addx 7\n\
-            noop\n\
-            noop\n\
-            noop\n\
-            addx 2\n\
-            addx 6\n\
-            noop\n\
-            noop\n\
-            noop\n\
-            noop\n\
-            noop\n\
-            addx 1\n\
-            noop\n\
-            noop\n\
-            addx 7\n\
-            addx 1\n\
-            noop\n\
-            addx -13\n\
-            addx 13\n\
-            addx 7\n\
-            noop\n\
-            addx 1\n\
-            addx -33\n\
-            noop\n\
-            noop\n\
-            noop\n\
-            addx 2\n\
-            noop\n\
-            noop\n\
-            noop\n\
-            addx 8\n\
-            noop\n\
-            addx -1\n\
-            addx 2\n\
-            addx 1\n\
-            noop\n\
-            addx 17\n\
-            addx -9\n\
-            addx 1\n\
-            addx 1\n\
-            addx -3\n\
-            addx 11\n\
-            noop\n\
-            noop\n\
-            addx 1\n\
-            noop\n\
-            addx 1\n\
-            noop\n\
-            noop\n\
-            addx -13\n\
-            addx -19\n\
-            addx 1\n\
-            addx 3\n\
-            addx 26\n\
-            addx -30\n\
-            addx 12\n\
-            addx -1\n\
-            addx 3\n\
-            addx 1\n\
-            noop\n\
-            noop\n\
-            noop\n\
-            addx -9\n\
-            addx 18\n\
-            addx 1\n\
-            addx 2\n\
-            noop\n\
-            noop\n\
-            addx 9\n\
-            noop\n\
-            noop\n\
-            noop\n\
-            addx -1\n\
-            addx 2\n\
-            addx -37\n\
-            addx 1\n\
-            addx 3\n\
-            noop\n\
-            addx 15\n\
-            addx -21\n\
-            addx 22\n\
-            addx -6\n\
-            addx 1\n\
-            noop\n\
-            addx 2\n\
-            addx 1\n\
-            noop\n\
-            addx -10\n\
-            noop\n\
-            noop\n\
-            addx 20\n\
-            addx 1\n\
-            addx 2\n\
-            addx 2\n\
-            addx -6\n\
-            addx -11\n\
-            noop\n\
-            noop\n\
-            noop\n\
-        ";
+        let input = include_str!("../../day-10-common/larger_example.txt");
 
         let sum = sum_signal_strength(input.as_bytes());
 
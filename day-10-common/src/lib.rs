@@ -0,0 +1,225 @@
+//! Emulator for the clock circuit of the handheld device from Day 10: Cathode-Ray Tube. Both parts
+//! of the puzzle observe the same CPU. What they are interested in differs, so the circuit reports
+//! the value of the X register during each cycle to a [`Probe`]. Probes can be combined, so a single
+//! run of the emulator answers both parts.
+
+use atoi::FromRadix10SignedChecked;
+
+pub struct ClockCircuit<I> {
+    instructions: I,
+    register: Option<i64>,
+    x: i64,
+    cycle: i64,
+}
+
+impl<I> ClockCircuit<I> {
+    pub fn new(instructions: I) -> Self {
+        Self {
+            x: 1,
+            cycle: 1,
+            register: None,
+            instructions,
+        }
+    }
+
+    pub fn tick(&mut self)
+    where
+        I: Iterator<Item = Instruction>,
+    {
+        if let Some(add_me) = self.register {
+            self.x += add_me;
+            self.register = None;
+        } else {
+            let inst = self.instructions.next().expect("yagni");
+            match inst {
+                Instruction::Noop => (),
+                Instruction::AddX(amount) => {
+                    self.register = Some(amount);
+                }
+            }
+        }
+        self.cycle += 1;
+    }
+
+    /// Value of the X register during the current cycle
+    pub fn x(&self) -> i64 {
+        self.x
+    }
+
+    /// Number of the current cycle. The first cycle is `1`.
+    pub fn cycle(&self) -> i64 {
+        self.cycle
+    }
+
+    /// Lets the probe observe the next `num_cycles` cycles.
+    pub fn run(&mut self, num_cycles: i64, probe: &mut impl Probe)
+    where
+        I: Iterator<Item = Instruction>,
+    {
+        for _ in 0..num_cycles {
+            probe.observe(self.cycle, self.x);
+            self.tick();
+        }
+    }
+}
+
+/// Observes the value of the X register during each cycle of the [`ClockCircuit`].
+pub trait Probe {
+    fn observe(&mut self, cycle: i64, x: i64);
+}
+
+impl<P> Probe for &mut P
+where
+    P: Probe,
+{
+    fn observe(&mut self, cycle: i64, x: i64) {
+        (**self).observe(cycle, x)
+    }
+}
+
+/// Feeds every cycle to both probes.
+impl<A, B> Probe for (A, B)
+where
+    A: Probe,
+    B: Probe,
+{
+    fn observe(&mut self, cycle: i64, x: i64) {
+        self.0.observe(cycle, x);
+        self.1.observe(cycle, x);
+    }
+}
+
+/// Sums up the signal strength during the 20th cycle and every 40 cycles after that.
+#[derive(Default)]
+pub struct SignalStrength {
+    sum: i64,
+}
+
+impl SignalStrength {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sum(&self) -> i64 {
+        self.sum
+    }
+}
+
+impl Probe for SignalStrength {
+    fn observe(&mut self, cycle: i64, x: i64) {
+        if cycle % 40 == 20 {
+            self.sum += cycle * x;
+        }
+    }
+}
+
+/// Screen 40 pixels wide and 6 high. Draws a single pixel each cycle. The pixel is lit (`#`) if the
+/// sprite, which is 3 pixels wide and centered around the X register, covers it. Otherwise it is
+/// dark (`.`).
+#[derive(Default)]
+pub struct Crt {
+    /// Drawn pixels, one line of text per row.
+    screen: Vec<u8>,
+}
+
+impl Crt {
+    const WIDTH: i64 = 40;
+    const HEIGHT: i64 = 6;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cycles it takes to draw the entire screen.
+    pub const fn num_cycles() -> i64 {
+        Self::WIDTH * Self::HEIGHT
+    }
+
+    /// Pixels drawn so far. Each row is terminated by a newline.
+    pub fn screen(&self) -> &[u8] {
+        &self.screen
+    }
+}
+
+impl Probe for Crt {
+    fn observe(&mut self, cycle: i64, x: i64) {
+        if cycle > Self::num_cycles() {
+            return;
+        }
+        let pos = (cycle - 1) % Self::WIDTH;
+        let pixel = if (x - pos).abs() <= 1 { b'#' } else { b'.' };
+        self.screen.push(pixel);
+        if pos == Self::WIDTH - 1 {
+            self.screen.push(b'\n');
+        }
+    }
+}
+
+pub enum Instruction {
+    Noop,
+    AddX(i64),
+}
+
+impl Instruction {
+    pub fn from_line(line: &[u8]) -> Self {
+        match &line[..4] {
+            b"noop" => Self::Noop,
+            b"addx" => {
+                let (amount, _) = i64::from_radix_10_signed_checked(&line[5..]);
+                Self::AddX(amount.expect("Invalid Addx syntax"))
+            }
+            _ => panic!("yagni"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClockCircuit, Crt, Instruction, SignalStrength};
+
+    #[test]
+    fn small_program() {
+        let input = ["noop", "addx 3", "addx -5"];
+        let instructions = input
+            .iter()
+            .map(|line| Instruction::from_line(line.as_bytes()));
+        let mut cc = ClockCircuit::new(instructions);
+
+        assert_eq!(1, cc.x());
+        cc.tick();
+        assert_eq!(1, cc.x());
+        cc.tick();
+        assert_eq!(1, cc.x());
+        cc.tick();
+        assert_eq!(4, cc.x());
+        cc.tick();
+        assert_eq!(4, cc.x());
+        cc.tick();
+        assert_eq!(-1, cc.x());
+        assert_eq!(6, cc.cycle());
+    }
+
+    #[test]
+    fn signal_strength_and_crt_in_one_pass() {
+        let input = include_str!("../larger_example.txt");
+        let instructions = input
+            .lines()
+            .map(|line| Instruction::from_line(line.as_bytes()));
+        let mut cc = ClockCircuit::new(instructions);
+        let mut probes = (SignalStrength::new(), Crt::new());
+
+        cc.run(Crt::num_cycles(), &mut probes);
+
+        let (signal_strength, crt) = probes;
+        assert_eq!(13140, signal_strength.sum());
+        let expected = b"\
+            ##..##..##..##..##..##..##..##..##..##..\n\
+            ###...###...###...###...###...###...###.\n\
+            ####....####....####....####....####....\n\
+            #####.....#####.....#####.....#####.....\n\
+            ######......######......######......####\n\
+            #######.......#######.......#######.....\n\
+        ";
+        assert_eq!(&expected[..], crt.screen());
+    }
+}
@@ -34,17 +34,17 @@
 //! This ends in a loss for you with a score of 1 (1 + 0).     The third round is a draw with both
 //! players choosing Scissors, giving you a score of 3 + 3 = 6.
 //!
-//! In this example, if you were to follow the strategy guide, you would get a total score of 15 (8
-//! + 1 + 6). What would your total score be if everything goes exactly according to your strategy
-//! guide?
+//! In this example, if you were to follow the strategy guide, you would get a total score of 15
+//! (8 + 1 + 6). What would your total score be if everything goes exactly according to your
+//! strategy guide?
 
 use std::{
     fs::File,
     io::{BufRead, BufReader},
-    iter,
 };
 
 use anyhow::Error;
+use day_2_common::{total_points, Interpretation, Ruleset};
 
 fn main() -> Result<(), Error> {
     let input = BufReader::new(File::open("input.txt")?);
@@ -53,110 +53,23 @@ fn main() -> Result<(), Error> {
     Ok(())
 }
 
-fn accumulate_points(mut input: impl BufRead) -> u64 {
-    let mut buffer = Vec::new();
-    let strategy_guide = iter::from_fn(|| {
-        buffer.clear();
-        input.read_until(b'\n', &mut buffer).unwrap();
-        Strategy::from_bytes(&buffer)
-    });
-    strategy_guide.map(Strategy::points).sum()
-}
-
-#[derive(Clone, Copy)]
-enum Shape {
-    Rock,
-    Paper,
-    Scissors,
-}
-
-impl Shape {
-    fn points(self) -> u64 {
-        match self {
-            Shape::Rock => 1,
-            Shape::Paper => 2,
-            Shape::Scissors => 3,
-        }
-    }
-
-    fn beats(self, other: Shape) -> bool {
-        matches!(
-            (self, other),
-            (Shape::Rock, Shape::Scissors)
-                | (Shape::Paper, Shape::Rock)
-                | (Shape::Scissors, Shape::Paper)
-        )
-    }
-}
-
-#[derive(Clone, Copy)]
-enum Outcome {
-    Win,
-    Draw,
-    Loose,
-}
-
-impl Outcome {
-    fn versus(you: Shape, opponent: Shape) -> Outcome {
-        if you.beats(opponent) {
-            Outcome::Win
-        } else if opponent.beats(you) {
-            Outcome::Loose
-        } else {
-            Outcome::Draw
-        }
-    }
-
-    fn points(self) -> u64 {
-        match self {
-            Outcome::Win => 6,
-            Outcome::Draw => 3,
-            Outcome::Loose => 0,
-        }
-    }
-}
-
-#[derive(Clone, Copy)]
-struct Strategy {
-    opponent: Shape,
-    you: Shape,
-}
-
-impl Strategy {
-    // Inupt e.g. b"A X" for Rock, Rock
-    fn from_bytes(bytes: &[u8]) -> Option<Self> {
-        if bytes.len() >= 3 {
-            let opponent = match bytes[0] {
-                b'A' => Shape::Rock,
-                b'B' => Shape::Paper,
-                b'C' => Shape::Scissors,
-                _ => return None,
-            };
-
-            let you = match bytes[2] {
-                b'X' => Shape::Rock,
-                b'Y' => Shape::Paper,
-                b'Z' => Shape::Scissors,
-                _ => return None,
-            };
-            Some(Self { you, opponent })
-        } else {
-            None
-        }
-    }
-
-    fn points(self) -> u64 {
-        Outcome::versus(self.you, self.opponent).points() + self.you.points()
-    }
+fn accumulate_points(input: impl BufRead) -> u64 {
+    total_points(input, &Ruleset::classic(), Interpretation::ShapeToPlay)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{accumulate_points, Strategy};
+    use day_2_common::{Interpretation, Round, Ruleset};
+
+    use crate::accumulate_points;
 
     #[test]
     fn points_strategy() {
-        let score = |bytes| Strategy::from_bytes(bytes).unwrap().points();
+        let rules = Ruleset::classic();
+        let score = |bytes| {
+            let round = Round::from_bytes(bytes, &rules).unwrap();
+            Interpretation::ShapeToPlay.points(&rules, round).unwrap()
+        };
         assert_eq!(score(b"A Y"), 8);
         assert_eq!(score(b"B X"), 1);
         assert_eq!(score(b"C Z"), 6);
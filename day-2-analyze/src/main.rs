@@ -0,0 +1,108 @@
+//! Compares both interpretations of the strategy guide in `input.txt` and finds the best response
+//! to how often the opponent plays each shape.
+//!
+//! ```
+//! analyze [--rules classic|rpsls] [--opponent <COUNTS>] [--mix <WEIGHTS>]
+//! ```
+//!
+//! `--rules` chooses between Rock Paper Scissors and Rock Paper Scissors Lizard Spock. `--opponent`
+//! takes the counts of each shape played by a hypothetical opponent and prints the best response to
+//! them in a section of its own, below the analysis of the guide. `--mix` additionally prints the
+//! expected score of a mixed strategy, playing each shape with a probability proportional to its
+//! weight, against the given opponent if any and the opponent of the guide otherwise. Both take one
+//! comma separated value per shape, in the order of the cycle, e.g. `1,1,2` for Rock, Paper and
+//! Scissors.
+
+use std::{env, fs::File, io::BufReader, str::FromStr};
+
+use anyhow::{anyhow, bail, Context, Error};
+use day_2_common::{
+    best_response, compare, strategy_guide, Frequencies, MixedStrategy, Ruleset, Shape,
+};
+
+fn main() -> Result<(), Error> {
+    let mut rules = Ruleset::classic();
+    let mut opponent = None;
+    let mut mix = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .with_context(|| format!("Missing value for {arg}"))?;
+        match arg.as_str() {
+            "--rules" => {
+                rules = match value.as_str() {
+                    "classic" => Ruleset::classic(),
+                    "rpsls" => Ruleset::rock_paper_scissors_lizard_spock(),
+                    _ => bail!("Unknown rules {value}, expected classic or rpsls"),
+                }
+            }
+            "--opponent" => opponent = Some(value),
+            "--mix" => mix = Some(value),
+            _ => bail!("Unknown argument {arg}"),
+        }
+    }
+    let opponent = opponent
+        .map(|counts| per_shape(&counts, &rules).map(Frequencies::new))
+        .transpose()?;
+    let mix = mix
+        .map(|weights| {
+            let weights: Vec<f64> = per_shape(&weights, &rules)?;
+            if weights.iter().any(|&weight| weight < 0.) || weights.iter().sum::<f64>() <= 0. {
+                bail!("Weights must not be negative and at least one must be positive");
+            }
+            Ok(MixedStrategy::new(weights))
+        })
+        .transpose()?;
+
+    let input = BufReader::new(File::open("input.txt").context("Can not open input file")?);
+    let comparison = compare(&rules, strategy_guide(input, &rules));
+    print!("{comparison}");
+    // Scores of the guide do not apply to another opponent, so only the best response is shown
+    if let Some(opponent) = &opponent {
+        let (shape, expected) = best_response(&rules, opponent);
+        println!();
+        println!(
+            "Given opponent:  {}",
+            percentages(&rules, |shape| opponent.probability(shape))
+        );
+        println!(
+            "Best response:   {}, {expected:.2} per round expected",
+            rules.name(shape)
+        );
+    }
+    if let Some(mix) = mix {
+        let against = opponent.as_ref().unwrap_or(&comparison.frequencies);
+        let expected = mix.expected_score(&rules, against);
+        println!(
+            "Mixed strategy:  {}, {expected:.2} per round expected",
+            percentages(&rules, |shape| mix.probability(shape))
+        );
+    }
+    Ok(())
+}
+
+/// E.g. `Rock 25.0%, Paper 25.0%, Scissors 50.0%`
+fn percentages(rules: &Ruleset, probability: impl Fn(Shape) -> f64) -> String {
+    let percentages: Vec<_> = rules
+        .shapes()
+        .map(|shape| format!("{} {:.1}%", rules.name(shape), probability(shape) * 100.))
+        .collect();
+    percentages.join(", ")
+}
+
+/// Parses one value per shape of the ruleset, e.g. `1,1,2`.
+fn per_shape<T: FromStr>(text: &str, rules: &Ruleset) -> Result<Vec<T>, Error> {
+    let values = text
+        .split(',')
+        .map(|value| value.trim().parse())
+        .collect::<Result<Vec<T>, _>>()
+        .map_err(|_| anyhow!("Invalid value in {text}"))?;
+    if values.len() != rules.num_shapes() {
+        bail!(
+            "Expected {} values separated by commas, got {text}",
+            rules.num_shapes()
+        );
+    }
+    Ok(values)
+}
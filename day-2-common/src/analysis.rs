@@ -0,0 +1,287 @@
+//! Game theory on top of a strategy guide. Given how often the opponent plays each shape, which
+//! response scores best, what do we expect from a mixed strategy, and how do both interpretations
+//! of the guide fare against each other.
+
+use std::fmt;
+
+use crate::{Interpretation, Round, Ruleset, Shape};
+
+/// How often the opponent plays each shape, i.e. the distribution of the first column.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frequencies {
+    /// Count per shape, by [`Shape::index`]. Shapes past the end have not been played.
+    counts: Vec<u64>,
+}
+
+impl Frequencies {
+    /// Counts in the order of [`Ruleset::shapes`].
+    pub fn new(counts: Vec<u64>) -> Self {
+        Self { counts }
+    }
+
+    pub fn add(&mut self, shape: Shape) {
+        if self.counts.len() <= shape.index() {
+            self.counts.resize(shape.index() + 1, 0);
+        }
+        self.counts[shape.index()] += 1;
+    }
+
+    pub fn count(&self, shape: Shape) -> u64 {
+        self.counts.get(shape.index()).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Relative frequency of the shape. `0` if no rounds have been counted at all.
+    pub fn probability(&self, shape: Shape) -> f64 {
+        match self.total() {
+            0 => 0.,
+            total => self.count(shape) as f64 / total as f64,
+        }
+    }
+}
+
+impl FromIterator<Round> for Frequencies {
+    fn from_iter<T: IntoIterator<Item = Round>>(rounds: T) -> Self {
+        let mut frequencies = Self::default();
+        for round in rounds {
+            frequencies.add(round.opponent);
+        }
+        frequencies
+    }
+}
+
+/// Plays each shape at random with a fixed probability.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MixedStrategy {
+    /// Probability per shape, by [`Shape::index`]. Sums up to `1`.
+    probabilities: Vec<f64>,
+}
+
+impl MixedStrategy {
+    /// Weights in the order of [`Ruleset::shapes`]. They need not sum up to `1`, e.g. `[1., 1., 2.]`
+    /// plays Scissors in half of the rounds of the classic game. Panics if a weight is negative or
+    /// all are zero.
+    pub fn new(weights: Vec<f64>) -> Self {
+        assert!(
+            weights.iter().all(|&weight| weight >= 0.),
+            "Weights must not be negative"
+        );
+        let sum: f64 = weights.iter().sum();
+        assert!(sum > 0., "At least one weight must be positive");
+        Self {
+            probabilities: weights.into_iter().map(|weight| weight / sum).collect(),
+        }
+    }
+
+    /// Always plays `shape`.
+    pub fn pure(shape: Shape) -> Self {
+        let mut weights = vec![0.; shape.index() + 1];
+        weights[shape.index()] = 1.;
+        Self::new(weights)
+    }
+
+    pub fn probability(&self, shape: Shape) -> f64 {
+        self.probabilities.get(shape.index()).copied().unwrap_or(0.)
+    }
+
+    /// Average points per round against an opponent playing with the given frequencies.
+    pub fn expected_score(&self, rules: &Ruleset, opponent: &Frequencies) -> f64 {
+        rules
+            .shapes()
+            .flat_map(|you| rules.shapes().map(move |other| (you, other)))
+            .map(|(you, other)| {
+                self.probability(you) * opponent.probability(other) * rules.score(you, other) as f64
+            })
+            .sum()
+    }
+}
+
+/// The single shape with the highest expected score per round against an opponent playing with
+/// the given frequencies, together with that score. Ties are resolved in the order of
+/// [`Ruleset::shapes`].
+///
+/// No mixed strategy can do better, since its expected score is just the weighted average of the
+/// expected scores of the shapes it mixes.
+pub fn best_response(rules: &Ruleset, opponent: &Frequencies) -> (Shape, f64) {
+    rules
+        .shapes()
+        .map(|shape| {
+            let expected = MixedStrategy::pure(shape).expected_score(rules, opponent);
+            (shape, expected)
+        })
+        .reduce(|best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .expect("A ruleset has at least three shapes")
+}
+
+/// Both interpretations of the same strategy guide, side by side.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison<'a> {
+    pub rules: &'a Ruleset,
+    pub num_rounds: u64,
+    pub frequencies: Frequencies,
+    /// Total score of part one
+    pub shape_to_play: u64,
+    /// Total score of part two
+    pub desired_outcome: u64,
+    /// Best response to the frequencies of the opponent and its expected score per round
+    pub best_response: (Shape, f64),
+}
+
+/// Scores the guide with both interpretations and compares them to the best response. Stops at the
+/// first round which is invalid in either interpretation.
+pub fn compare(rules: &Ruleset, rounds: impl IntoIterator<Item = Round>) -> Comparison<'_> {
+    let mut num_rounds = 0;
+    let mut frequencies = Frequencies::default();
+    let mut shape_to_play = 0;
+    let mut desired_outcome = 0;
+    for round in rounds {
+        let (Some(shape), Some(outcome)) = (
+            Interpretation::ShapeToPlay.points(rules, round),
+            Interpretation::DesiredOutcome.points(rules, round),
+        ) else {
+            break;
+        };
+        num_rounds += 1;
+        frequencies.add(round.opponent);
+        shape_to_play += shape;
+        desired_outcome += outcome;
+    }
+    Comparison {
+        rules,
+        num_rounds,
+        best_response: best_response(rules, &frequencies),
+        frequencies,
+        shape_to_play,
+        desired_outcome,
+    }
+}
+
+/// E.g.
+///
+/// ```text
+/// Opponent: Rock 33.3%, Paper 33.3%, Scissors 33.3% in 3 rounds
+/// Shape to play:   15 points, 5.00 per round
+/// Desired outcome: 12 points, 4.00 per round
+/// Best response:   Scissors, 6.00 per round expected
+/// ```
+impl fmt::Display for Comparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rounds = self.num_rounds;
+        let per_round = |points: u64| match rounds {
+            0 => 0.,
+            rounds => points as f64 / rounds as f64,
+        };
+        write!(f, "Opponent:")?;
+        for (index, shape) in self.rules.shapes().enumerate() {
+            let separator = if index == 0 { " " } else { ", " };
+            let name = self.rules.name(shape);
+            let percent = self.frequencies.probability(shape) * 100.;
+            write!(f, "{separator}{name} {percent:.1}%")?;
+        }
+        writeln!(f, " in {rounds} rounds")?;
+        writeln!(
+            f,
+            "Shape to play:   {} points, {:.2} per round",
+            self.shape_to_play,
+            per_round(self.shape_to_play)
+        )?;
+        writeln!(
+            f,
+            "Desired outcome: {} points, {:.2} per round",
+            self.desired_outcome,
+            per_round(self.desired_outcome)
+        )?;
+        let (shape, expected) = self.best_response;
+        let name = self.rules.name(shape);
+        writeln!(
+            f,
+            "Best response:   {name}, {expected:.2} per round expected"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{best_response, compare, strategy_guide, Frequencies, MixedStrategy, Ruleset};
+
+    const GUIDE: &str = "\
+        A Y\n\
+        B X\n\
+        C Z\n\
+    ";
+
+    #[test]
+    fn best_response_to_frequencies() {
+        let rules = Ruleset::classic();
+
+        // Mostly Rock, so Paper wins most rounds
+        let (shape, expected) = best_response(&rules, &Frequencies::new(vec![3, 1, 0]));
+        assert_eq!(rules.shape("Paper"), Some(shape));
+        assert_close((8. * 3. + 5.) / 4., expected);
+
+        // Mostly Scissors, so Rock wins most rounds
+        let (shape, _) = best_response(&rules, &Frequencies::new(vec![1, 0, 5]));
+        assert_eq!(rules.shape("Rock"), Some(shape));
+    }
+
+    #[test]
+    fn expected_score_of_mixed_strategies() {
+        let rules = Ruleset::classic();
+        let uniform = Frequencies::new(vec![1, 1, 1]);
+        let expected = |strategy: MixedStrategy| strategy.expected_score(&rules, &uniform);
+        let shape = |name| rules.shape(name).unwrap();
+
+        // Against a uniform opponent every shape wins, draws and looses equally often
+        assert_close(4., expected(MixedStrategy::pure(shape("Rock"))));
+        assert_close(5., expected(MixedStrategy::pure(shape("Paper"))));
+        let mixed = MixedStrategy::new(vec![1., 1., 2.]);
+        assert_eq!(0.5, mixed.probability(shape("Scissors")));
+        assert_close((4. + 5. + 2. * 6.) / 4., expected(mixed.clone()));
+        assert_eq!(0., mixed.expected_score(&rules, &Frequencies::default()));
+    }
+
+    #[test]
+    fn compare_interpretations() {
+        let rules = Ruleset::classic();
+        let comparison = compare(&rules, strategy_guide(GUIDE.as_bytes(), &rules));
+
+        assert_eq!(3, comparison.num_rounds);
+        assert_eq!(15, comparison.shape_to_play);
+        assert_eq!(12, comparison.desired_outcome);
+        assert_eq!(rules.shape("Scissors"), Some(comparison.best_response.0));
+        assert_eq!(
+            "Opponent: Rock 33.3%, Paper 33.3%, Scissors 33.3% in 3 rounds\n\
+            Shape to play:   15 points, 5.00 per round\n\
+            Desired outcome: 12 points, 4.00 per round\n\
+            Best response:   Scissors, 6.00 per round expected\n",
+            comparison.to_string()
+        );
+    }
+
+    #[test]
+    fn rock_paper_scissors_lizard_spock_against_uniform_opponent() {
+        let rules = Ruleset::rock_paper_scissors_lizard_spock();
+        let uniform = Frequencies::new(vec![1; 5]);
+
+        // Every shape wins and looses twice and draws once, so the most valuable one is best
+        let (shape, expected) = best_response(&rules, &uniform);
+        assert_eq!(rules.shape("Spock"), Some(shape));
+        assert_close(5. + (2. * 6. + 3.) / 5., expected);
+    }
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Rock paper scissors for Day 2: Rock Paper Scissors. Both parts read the same strategy guide, but
+//! disagree on what its second column means. Part one takes it for the shape to play, part two for
+//! the desired outcome of the round. Here this is just an [`Interpretation`] of a [`Round`].
+//!
+//! Shapes, letters and points are not hard-coded but given by a [`Ruleset`], so the same scorer
+//! also plays Rock Paper Scissors Lizard Spock.
+
+use std::{io::BufRead, iter};
+
+pub use self::{
+    analysis::{best_response, compare, Comparison, Frequencies, MixedStrategy},
+    ruleset::{InvalidRuleset, OutcomeRule, Ruleset, Shape, ShapeRule},
+};
+
+mod analysis;
+mod ruleset;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loose,
+}
+
+/// One line of the strategy guide
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Round {
+    pub opponent: Shape,
+    /// The encrypted second column. Its meaning depends on the [`Interpretation`].
+    pub column: u8,
+}
+
+impl Round {
+    // Input e.g. b"A X" for Rock, X
+    pub fn from_bytes(bytes: &[u8], rules: &Ruleset) -> Option<Self> {
+        if bytes.len() >= 3 {
+            let opponent = rules.opponent(bytes[0])?;
+            Some(Self {
+                opponent,
+                column: bytes[2],
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// What the second column of the strategy guide means.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interpretation {
+    /// Part one: e.g. `X` for Rock, `Y` for Paper and `Z` for Scissors.
+    ShapeToPlay,
+    /// Part two: e.g. `X` means you need to lose, `Y` to draw and `Z` to win.
+    DesiredOutcome,
+}
+
+impl Interpretation {
+    /// The shape you play in the round. `None` if the second column means nothing in this
+    /// interpretation.
+    pub fn response(self, rules: &Ruleset, round: Round) -> Option<Shape> {
+        match self {
+            Interpretation::ShapeToPlay => rules.response(round.column),
+            Interpretation::DesiredOutcome => {
+                let desired = rules.outcome(round.column)?;
+                Some(rules.shape_for_outcome(round.opponent, desired))
+            }
+        }
+    }
+
+    pub fn points(self, rules: &Ruleset, round: Round) -> Option<u64> {
+        let you = self.response(rules, round)?;
+        Some(rules.score(you, round.opponent))
+    }
+}
+
+/// Rounds of the strategy guide. Stops at the first line which is not a valid round.
+pub fn strategy_guide<'a>(
+    mut input: impl BufRead + 'a,
+    rules: &'a Ruleset,
+) -> impl Iterator<Item = Round> + 'a {
+    let mut buffer = Vec::new();
+    iter::from_fn(move || {
+        buffer.clear();
+        input.read_until(b'\n', &mut buffer).unwrap();
+        Round::from_bytes(&buffer, rules)
+    })
+}
+
+/// Total score if everything goes exactly according to the strategy guide. Stops at the first line
+/// which is not a valid round in this interpretation.
+pub fn total_points(input: impl BufRead, rules: &Ruleset, interpretation: Interpretation) -> u64 {
+    strategy_guide(input, rules)
+        .map_while(|round| interpretation.points(rules, round))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{total_points, Interpretation, Round, Ruleset};
+
+    #[test]
+    fn interpretations_of_second_column() {
+        let rules = Ruleset::classic();
+        let round = Round::from_bytes(b"A Y", &rules).unwrap();
+
+        assert_eq!(
+            rules.shape("Paper"),
+            Interpretation::ShapeToPlay.response(&rules, round)
+        );
+        assert_eq!(
+            rules.shape("Rock"),
+            Interpretation::DesiredOutcome.response(&rules, round)
+        );
+        assert_eq!(None, Round::from_bytes(b"D X", &rules));
+        assert_eq!(None, Round::from_bytes(b"A", &rules));
+    }
+
+    #[test]
+    fn rock_paper_scissors_lizard_spock_guide() {
+        let rules = Ruleset::rock_paper_scissors_lizard_spock();
+        let guide = "\
+            A Z\n\
+            E V\n\
+            D Y\n\
+        ";
+
+        // Spock vaporizes Rock, Rock looses against Spock, Lizard draws against Lizard
+        assert_eq!(
+            (5 + 6) + 1 + (4 + 3),
+            total_points(guide.as_bytes(), &rules, Interpretation::ShapeToPlay)
+        );
+        // Spock scores more than Paper for the win. `V` is no outcome, so the guide ends there.
+        assert_eq!(
+            5 + 6,
+            total_points(guide.as_bytes(), &rules, Interpretation::DesiredOutcome)
+        );
+    }
+}
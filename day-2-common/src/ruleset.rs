@@ -0,0 +1,340 @@
+//! Shapes, letters and points as data. The shapes form a cycle in which every shape beats the
+//! `(n - 1) / 2` shapes before it and looses against the ones after it. With an odd number of
+//! shapes this is a fair game: each shape beats exactly as many shapes as it looses against.
+
+use std::{error::Error, fmt};
+
+use crate::Outcome;
+
+/// A shape of a [`Ruleset`]. Only meaningful together with the ruleset it has been taken from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Shape(usize);
+
+impl Shape {
+    /// Position of the shape in the cycle of its ruleset
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Name, letters and points of a single shape.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShapeRule {
+    pub name: String,
+    /// Letter in the first column of the strategy guide, e.g. `A` for Rock.
+    pub opponent: u8,
+    /// Letter in the second column, if it is read as the shape to play, e.g. `X` for Rock.
+    pub response: u8,
+    /// Points for playing the shape
+    pub points: u64,
+}
+
+impl ShapeRule {
+    pub fn new(name: &str, opponent: u8, response: u8, points: u64) -> Self {
+        Self {
+            name: name.to_owned(),
+            opponent,
+            response,
+            points,
+        }
+    }
+}
+
+/// Letter in the second column, if it is read as the desired outcome, and the points for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutcomeRule {
+    pub letter: u8,
+    pub points: u64,
+}
+
+/// Reason why shapes and outcomes do not make up a [`Ruleset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidRuleset {
+    /// Shapes can only beat as many shapes as they loose against if their number is odd. A single
+    /// shape would only ever draw.
+    NumberOfShapes(usize),
+    /// The same letter stands for two different shapes or outcomes in the same column.
+    DuplicateLetter(u8),
+}
+
+impl fmt::Display for InvalidRuleset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidRuleset::NumberOfShapes(num) => {
+                write!(f, "number of shapes must be odd and at least 3, not {num}")
+            }
+            InvalidRuleset::DuplicateLetter(letter) => {
+                write!(f, "letter '{}' is used twice", letter.escape_ascii())
+            }
+        }
+    }
+}
+
+impl Error for InvalidRuleset {}
+
+/// Shapes, which of them beats which, the letters of the strategy guide and the points.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ruleset {
+    /// In the order of the cycle
+    shapes: Vec<ShapeRule>,
+    /// In the order loose, draw, win
+    outcomes: [OutcomeRule; 3],
+}
+
+impl Ruleset {
+    /// `shapes` in the order of the cycle, i.e. each shape beats its predecessor. `outcomes` in the
+    /// order loose, draw, win.
+    pub fn new(shapes: Vec<ShapeRule>, outcomes: [OutcomeRule; 3]) -> Result<Self, InvalidRuleset> {
+        if shapes.len() < 3 || shapes.len().is_multiple_of(2) {
+            return Err(InvalidRuleset::NumberOfShapes(shapes.len()));
+        }
+        let columns = [
+            shapes
+                .iter()
+                .map(|shape| shape.opponent)
+                .collect::<Vec<_>>(),
+            shapes.iter().map(|shape| shape.response).collect(),
+            outcomes.iter().map(|outcome| outcome.letter).collect(),
+        ];
+        for letters in columns {
+            for (index, &letter) in letters.iter().enumerate() {
+                if letters[..index].contains(&letter) {
+                    return Err(InvalidRuleset::DuplicateLetter(letter));
+                }
+            }
+        }
+        Ok(Self { shapes, outcomes })
+    }
+
+    /// Rock, Paper and Scissors with the letters and points of the puzzle.
+    pub fn classic() -> Self {
+        Self::new(
+            vec![
+                ShapeRule::new("Rock", b'A', b'X', 1),
+                ShapeRule::new("Paper", b'B', b'Y', 2),
+                ShapeRule::new("Scissors", b'C', b'Z', 3),
+            ],
+            Self::OUTCOMES,
+        )
+        .unwrap()
+    }
+
+    /// Rock, Paper, Scissors, Lizard and Spock. The letters `A` through `E` and `V` through `Z`
+    /// and the points `1` through `5` follow the order of the name.
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::new(
+            vec![
+                ShapeRule::new("Rock", b'A', b'V', 1),
+                ShapeRule::new("Spock", b'E', b'Z', 5),
+                ShapeRule::new("Paper", b'B', b'W', 2),
+                ShapeRule::new("Lizard", b'D', b'Y', 4),
+                ShapeRule::new("Scissors", b'C', b'X', 3),
+            ],
+            Self::OUTCOMES,
+        )
+        .unwrap()
+    }
+
+    /// `X` to loose, `Y` to draw and `Z` to win, like in the puzzle.
+    const OUTCOMES: [OutcomeRule; 3] = [
+        OutcomeRule {
+            letter: b'X',
+            points: 0,
+        },
+        OutcomeRule {
+            letter: b'Y',
+            points: 3,
+        },
+        OutcomeRule {
+            letter: b'Z',
+            points: 6,
+        },
+    ];
+
+    pub fn num_shapes(&self) -> usize {
+        self.shapes.len()
+    }
+
+    /// All shapes, in the order of the cycle
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.shapes.len()).map(Shape)
+    }
+
+    /// Shape with the given name
+    pub fn shape(&self, name: &str) -> Option<Shape> {
+        self.shapes
+            .iter()
+            .position(|shape| shape.name == name)
+            .map(Shape)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.shapes[shape.0].name
+    }
+
+    /// Shape the opponent plays, by the letter in the first column
+    pub fn opponent(&self, letter: u8) -> Option<Shape> {
+        self.shapes
+            .iter()
+            .position(|shape| shape.opponent == letter)
+            .map(Shape)
+    }
+
+    /// Shape to play, by the letter in the second column
+    pub fn response(&self, letter: u8) -> Option<Shape> {
+        self.shapes
+            .iter()
+            .position(|shape| shape.response == letter)
+            .map(Shape)
+    }
+
+    /// Desired outcome, by the letter in the second column
+    pub fn outcome(&self, letter: u8) -> Option<Outcome> {
+        let index = self
+            .outcomes
+            .iter()
+            .position(|outcome| outcome.letter == letter)?;
+        Some([Outcome::Loose, Outcome::Draw, Outcome::Win][index])
+    }
+
+    pub fn beats(&self, shape: Shape, other: Shape) -> bool {
+        let num = self.shapes.len();
+        let distance = (shape.0 + num - other.0) % num;
+        (1..=num / 2).contains(&distance)
+    }
+
+    pub fn versus(&self, you: Shape, opponent: Shape) -> Outcome {
+        if self.beats(you, opponent) {
+            Outcome::Win
+        } else if self.beats(opponent, you) {
+            Outcome::Loose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    pub fn shape_points(&self, shape: Shape) -> u64 {
+        self.shapes[shape.0].points
+    }
+
+    pub fn outcome_points(&self, outcome: Outcome) -> u64 {
+        let index = match outcome {
+            Outcome::Loose => 0,
+            Outcome::Draw => 1,
+            Outcome::Win => 2,
+        };
+        self.outcomes[index].points
+    }
+
+    /// Points you get for a single round, playing `you` against `opponent`.
+    pub fn score(&self, you: Shape, opponent: Shape) -> u64 {
+        self.outcome_points(self.versus(you, opponent)) + self.shape_points(you)
+    }
+
+    /// Shape to play against `opponent` so the round ends with the `desired` outcome. If several
+    /// shapes do, the one with the most points.
+    pub fn shape_for_outcome(&self, opponent: Shape, desired: Outcome) -> Shape {
+        self.shapes()
+            .filter(|&candidate| self.versus(candidate, opponent) == desired)
+            .fold(None, |best: Option<Shape>, candidate| match best {
+                Some(best) if self.shape_points(best) >= self.shape_points(candidate) => Some(best),
+                _ => Some(candidate),
+            })
+            .expect("Every outcome is possible against every shape")
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InvalidRuleset, OutcomeRule, Ruleset, ShapeRule};
+    use crate::Outcome;
+
+    #[test]
+    fn classic_beats() {
+        let rules = Ruleset::classic();
+        let [rock, paper, scissors] =
+            ["Rock", "Paper", "Scissors"].map(|name| rules.shape(name).unwrap());
+
+        assert!(rules.beats(rock, scissors));
+        assert!(rules.beats(paper, rock));
+        assert!(rules.beats(scissors, paper));
+        assert!(!rules.beats(rock, paper));
+        assert!(!rules.beats(rock, rock));
+        assert_eq!(8, rules.score(paper, rock));
+        assert_eq!(1, rules.score(rock, paper));
+        assert_eq!(6, rules.score(scissors, scissors));
+    }
+
+    #[test]
+    fn rock_paper_scissors_lizard_spock_beats() {
+        let rules = Ruleset::rock_paper_scissors_lizard_spock();
+        let shape = |name| rules.shape(name).unwrap();
+        let wins = [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ];
+
+        for you in rules.shapes() {
+            for other in rules.shapes() {
+                let expected = wins.contains(&(rules.name(you), rules.name(other)));
+                assert_eq!(expected, rules.beats(you, other));
+            }
+        }
+        // Both Paper and Spock beat Rock, Spock scores more
+        assert_eq!(
+            shape("Spock"),
+            rules.shape_for_outcome(shape("Rock"), Outcome::Win)
+        );
+        assert_eq!(Some(shape("Lizard")), rules.opponent(b'D'));
+        assert_eq!(Some(shape("Lizard")), rules.response(b'Y'));
+    }
+
+    #[test]
+    fn invalid_rulesets() {
+        let outcomes = [b'X', b'Y', b'Z'].map(|letter| OutcomeRule { letter, points: 0 });
+        let shapes = |letters: &[(u8, u8)]| {
+            letters
+                .iter()
+                .map(|&(opponent, response)| ShapeRule::new("Shape", opponent, response, 1))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            Err(InvalidRuleset::NumberOfShapes(4)),
+            Ruleset::new(
+                shapes(&[(b'A', b'W'), (b'B', b'X'), (b'C', b'Y'), (b'D', b'Z')]),
+                outcomes
+            )
+        );
+        assert_eq!(
+            Err(InvalidRuleset::NumberOfShapes(1)),
+            Ruleset::new(shapes(&[(b'A', b'X')]), outcomes)
+        );
+        assert_eq!(
+            Err(InvalidRuleset::DuplicateLetter(b'X')),
+            Ruleset::new(
+                shapes(&[(b'A', b'X'), (b'B', b'Y'), (b'C', b'X')]),
+                outcomes
+            )
+        );
+        assert!(Ruleset::new(
+            shapes(&[(b'A', b'A'), (b'B', b'B'), (b'C', b'C')]),
+            outcomes
+        )
+        .is_ok());
+    }
+}
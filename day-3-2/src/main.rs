@@ -79,153 +79,76 @@
 //! corresponds to the badges of each three-Elf group. What is the sum of the priorities of those
 //! item types?
 
-use anyhow::Error;
+use anyhow::{bail, Error};
+use day_3_common::{checked_badges, priority, ValidationError};
 use std::{
+    env,
     fs::File,
     io::{BufRead, BufReader},
 };
 
+/// Number of Elves in each group, unless specified as first argument
+const DEFAULT_GROUP_SIZE: usize = 3;
+
 fn main() -> Result<(), Error> {
+    let group_size = match env::args().nth(1) {
+        Some(arg) => arg.parse()?,
+        None => DEFAULT_GROUP_SIZE,
+    };
+    if group_size == 0 {
+        bail!("Group size must be at least 1");
+    }
     let input = BufReader::new(File::open("input.txt")?);
-    let sum = sum_of_priorities(input);
+    let sum = sum_of_priorities(input, group_size)?;
     println!("{sum}");
     Ok(())
 }
 
-fn sum_of_priorities(input: impl BufRead) -> u64 {
-    let mut sum = 0;
-    let mut lines = LineStream::new(input);
-    while let Some(rucksack) = lines.next() {
-        let mut common = Common::new();
-        common.update(rucksack);
-        common.update(lines.next().unwrap_or(b""));
-        common.update(lines.next().unwrap_or(b""));
-
-        sum += common.first_priority() as u64;
-    }
-    sum
-}
-
-const fn priority(item: u8) -> u8 {
-    match item {
-        b'a'..=b'z' => item - b'a' + 1,
-        b'A'..=b'Z' => item - b'A' + 27,
-        _ => 0,
-    }
-}
-
-/// Keeps track of common items
-struct Common {
-    /// Item appeard in all sets presented so far.
-    ///
-    /// Since there are only 52 different items in total we use a static array instead of a set. +1
-    /// is needed for the `0` we use to model invalid input and error cases
-    all: [bool; priority(b'Z') as usize + 1],
-}
-
-impl Common {
-    fn new() -> Self {
-        Self {
-            all: [true; priority(b'Z') as usize + 1],
-        }
-    }
-
-    fn update(&mut self, items: &[u8]) {
-        let mut appeared = [false; priority(b'Z') as usize + 1];
-        for &item in items {
-            appeared[priority(item) as usize] = true;
-        }
-
-        for (all, current) in self.all.iter_mut().zip(appeared) {
-            *all = *all && current;
-        }
-    }
-
-    /// Priority of first item in common by any list of items seen so far, or 0
-    fn first_priority(&self) -> u8 {
-        self.all
-            .iter()
-            .copied()
-            .position(|present| present)
-            .unwrap_or(0) as u8
-    }
-}
-
-struct LineStream<R> {
-    input: R,
-    buffer: Vec<u8>,
-}
-
-impl<R> LineStream<R> {
-    fn new(input: R) -> Self {
-        Self {
-            input,
-            buffer: Vec::new(),
-        }
-    }
-
-    fn next(&mut self) -> Option<&'_ [u8]>
-    where
-        R: BufRead,
-    {
-        self.buffer.clear();
-        self.input.read_until(b'\n', &mut self.buffer).ok()?;
-        if self.buffer.is_empty() {
-            None
-        } else {
-            self.buffer.pop();
-            Some(&self.buffer)
-        }
-    }
+fn sum_of_priorities(input: impl BufRead, group_size: usize) -> Result<u64, ValidationError> {
+    checked_badges(input, group_size)
+        .map(|badge| badge.map(|badge| priority(badge) as u64))
+        .sum()
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{priority, sum_of_priorities, Common};
+    use day_3_common::{InvalidGroup, ValidationError};
 
-    #[test]
-    fn first_test_elf_badge_priority() {
-        let mut common = Common::new();
-        common.update(b"vJrwpWtwJgWrhcsFMMfFFhFp");
-        common.update(b"jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL");
-        common.update(b"PmmdzqPrVvPwwTWBwg");
+    use crate::sum_of_priorities;
 
-        assert_eq!(common.first_priority(), priority(b'r'));
-    }
+    const INPUT: &str = "\
+        vJrwpWtwJgWrhcsFMMfFFhFp\n\
+        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+        PmmdzqPrVvPwwTWBwg\n\
+        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n\
+        ttgJtRGJQctTZtZT\n\
+        CrZsJsPPZsGzwwsLwLmpwMDw\n\
+    ";
 
     #[test]
-    fn second_test_elf_badge_priority() {
-        let mut common = Common::new();
-        common.update(b"wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn");
-        common.update(b"ttgJtRGJQctTZtZT");
-        common.update(b"CrZsJsPPZsGzwwsLwLmpwMDw");
+    fn rucksack_sum_of_priorities() {
+        let actual = sum_of_priorities(INPUT.as_bytes(), 3).unwrap();
 
-        assert_eq!(common.first_priority(), priority(b'Z'));
+        assert_eq!(70, actual)
     }
 
     #[test]
-    fn priorities() {
-        assert_eq!(16, priority(b'p'));
-        assert_eq!(38, priority(b'L'));
-        assert_eq!(42, priority(b'P'));
-        assert_eq!(22, priority(b'v'));
-        assert_eq!(20, priority(b't'));
-        assert_eq!(19, priority(b's'));
+    fn other_group_sizes() {
+        let pairs = "abcd\nAbCD\nxyZz\nZ\n";
+
+        assert_eq!(2 + 52, sum_of_priorities(pairs.as_bytes(), 2).unwrap());
     }
 
     #[test]
-    fn rucksack_sum_of_priorities() {
-        let input = "\
-           vJrwpWtwJgWrhcsFMMfFFhFp\n\
-           jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
-           PmmdzqPrVvPwwTWBwg\n\
-           wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n\
-           ttgJtRGJQctTZtZT\n\
-           CrZsJsPPZsGzwwsLwLmpwMDw\n\
-        ";
-
-        let actual = sum_of_priorities(input.as_bytes());
-
-        assert_eq!(70, actual)
+    fn group_without_badge() {
+        let error = sum_of_priorities(INPUT.as_bytes(), 6).unwrap_err();
+
+        assert_eq!(
+            ValidationError::Group {
+                line: 1,
+                reason: InvalidGroup::NoBadge
+            },
+            error
+        );
     }
 }
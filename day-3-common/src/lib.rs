@@ -0,0 +1,231 @@
+//! Rucksack items of Day 3: Rucksack Reorganization. Part one looks for the item both compartments
+//! of a rucksack have in common, part two for the badge all rucksacks of a group have in common.
+//!
+//! There are only 52 different items, so any set of them fits into the bits of a single `u64`.
+//! Finding the items in common is then just a bitwise and.
+
+use std::{fmt, io::BufRead};
+
+use common::LineStream;
+
+pub use self::validate::{
+    checked_badges, checked_items, checked_shared_item, checked_shared_items, InvalidGroup,
+    InvalidRucksack, ValidationError,
+};
+
+mod validate;
+
+/// Priority of an item. `a` through `z` have priorities 1 through 26, `A` through `Z` 27 through
+/// 52. `0` for bytes which are not an item.
+pub const fn priority(item: u8) -> u8 {
+    match item {
+        b'a'..=b'z' => item - b'a' + 1,
+        b'A'..=b'Z' => item - b'A' + 27,
+        _ => 0,
+    }
+}
+
+/// Item with the given priority. Inverse of [`priority`].
+pub const fn item(priority: u8) -> u8 {
+    match priority {
+        1..=26 => b'a' + priority - 1,
+        27..=52 => b'A' + priority - 27,
+        _ => panic!("Priority must be in 1..=52"),
+    }
+}
+
+/// Set of items. Each item is represented by the bit matching its priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    /// No items at all
+    pub const EMPTY: ItemSet = ItemSet(0);
+    /// Each of the 52 items
+    pub const ALL: ItemSet = ItemSet(((1 << 53) - 1) & !1);
+
+    /// Set of all items in the list. Bytes which are not an item are ignored.
+    pub fn from_items(items: &[u8]) -> Self {
+        let mut set = Self::EMPTY;
+        for &item in items {
+            set.insert(item);
+        }
+        set
+    }
+
+    /// Adds an item to the set. Bytes which are not an item are ignored.
+    pub fn insert(&mut self, item: u8) {
+        self.0 |= Self::bit(item);
+    }
+
+    pub fn contains(&self, item: u8) -> bool {
+        self.0 & Self::bit(item) != 0
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    /// Number of items in the set
+    pub fn len(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Lowest priority of any item in the set
+    pub fn first_priority(&self) -> Option<u8> {
+        (!self.is_empty()).then(|| self.0.trailing_zeros() as u8)
+    }
+
+    /// Items in the set, ordered by priority
+    pub fn items(&self) -> impl Iterator<Item = u8> {
+        let bits = self.0;
+        (1..=52).filter(move |p| bits & (1 << p) != 0).map(item)
+    }
+
+    fn bit(item: u8) -> u64 {
+        match priority(item) {
+            0 => 0,
+            priority => 1 << priority,
+        }
+    }
+}
+
+/// Items in order of priority, e.g. `prP`
+impl fmt::Display for ItemSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in self.items() {
+            write!(f, "{}", item as char)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<u8> for ItemSet {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut set = Self::EMPTY;
+        for item in iter {
+            set.insert(item);
+        }
+        set
+    }
+}
+
+/// Items both compartments of the rucksack have in common. The first half of the items is in the
+/// first compartment, the second half in the second.
+pub fn shared_items(rucksack: &[u8]) -> ItemSet {
+    let (first, second) = rucksack.split_at(rucksack.len() / 2);
+    ItemSet::from_items(first).intersection(ItemSet::from_items(second))
+}
+
+/// Items all rucksacks of the group have in common.
+pub fn badges<'a>(group: impl IntoIterator<Item = &'a [u8]>) -> ItemSet {
+    group
+        .into_iter()
+        .map(ItemSet::from_items)
+        .fold(ItemSet::ALL, ItemSet::intersection)
+}
+
+/// Calls `f` for each group of `group_size` consecutive rucksacks in the input. The last group may
+/// be smaller, if the number of rucksacks is not a multiple of `group_size`. Panics if `group_size`
+/// is `0`.
+pub fn for_each_group(input: impl BufRead, group_size: usize, mut f: impl FnMut(&[&[u8]])) {
+    assert!(group_size > 0, "A group must hold at least one rucksack");
+    let mut lines = LineStream::with_num_lines(input, group_size);
+    while let Some(rucksacks) = lines.next_line() {
+        let group: Vec<&[u8]> = rucksacks.split(|&byte| byte == b'\n').collect();
+        f(&group);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{badges, for_each_group, item, priority, shared_items, ItemSet};
+
+    const INPUT: &str = "\
+        vJrwpWtwJgWrhcsFMMfFFhFp\n\
+        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+        PmmdzqPrVvPwwTWBwg\n\
+        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n\
+        ttgJtRGJQctTZtZT\n\
+        CrZsJsPPZsGzwwsLwLmpwMDw\n\
+    ";
+
+    #[test]
+    fn priorities() {
+        assert_eq!(16, priority(b'p'));
+        assert_eq!(38, priority(b'L'));
+        assert_eq!(42, priority(b'P'));
+        assert_eq!(22, priority(b'v'));
+        assert_eq!(20, priority(b't'));
+        assert_eq!(19, priority(b's'));
+        assert_eq!(0, priority(b'1'));
+        for item_ in (b'a'..=b'z').chain(b'A'..=b'Z') {
+            assert_eq!(item_, item(priority(item_)));
+        }
+    }
+
+    #[test]
+    fn set_operations() {
+        let abc = ItemSet::from_items(b"abcabc");
+        let bcd: ItemSet = b"BbcdZ".iter().copied().collect();
+
+        assert_eq!(3, abc.len());
+        assert!(abc.contains(b'a'));
+        assert!(!abc.contains(b'A'));
+        assert_eq!(
+            vec![b'b', b'c'],
+            abc.intersection(bcd).items().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            b"abcdBZ".to_vec(),
+            abc.union(bcd).items().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(2), bcd.first_priority());
+        assert_eq!(None, ItemSet::EMPTY.first_priority());
+        assert_eq!(52, ItemSet::ALL.len());
+        assert!(ItemSet::from_items(b"1-!").is_empty());
+    }
+
+    #[test]
+    fn shared_items_of_compartments() {
+        let shared = |rucksack: &[u8]| shared_items(rucksack).items().collect::<Vec<_>>();
+
+        assert_eq!(vec![b'p'], shared(b"vJrwpWtwJgWrhcsFMMfFFhFp"));
+        assert_eq!(vec![b'L'], shared(b"jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL"));
+        assert_eq!(vec![b'P'], shared(b"PmmdzqPrVvPwwTWBwg"));
+        assert_eq!(vec![b'v'], shared(b"wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn"));
+        assert_eq!(vec![b't'], shared(b"ttgJtRGJQctTZtZT"));
+        assert_eq!(vec![b's'], shared(b"CrZsJsPPZsGzwwsLwLmpwMDw"));
+    }
+
+    #[test]
+    fn badges_of_groups() {
+        let badges_of = |group_size| {
+            let mut found = Vec::new();
+            for_each_group(INPUT.as_bytes(), group_size, |group| {
+                found.push(badges(group.iter().copied()).items().collect::<Vec<_>>())
+            });
+            found
+        };
+
+        assert_eq!(vec![b"r".to_vec(), b"Z".to_vec()], badges_of(3));
+        assert_eq!(b"frsFM".to_vec(), badges_of(2)[0]);
+        // Last group holds the remaining two rucksacks
+        assert_eq!(2, badges_of(4).len());
+        assert_eq!(badges_of(2)[2], badges_of(4)[1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "A group must hold at least one rucksack")]
+    fn empty_groups() {
+        for_each_group(INPUT.as_bytes(), 0, |_| ());
+    }
+}
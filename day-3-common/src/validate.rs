@@ -0,0 +1,343 @@
+//! Strict checks of the puzzle input. The lenient functions in the crate root ignore anything which
+//! is not an item and return empty sets if nothing is shared. Here each of these cases is reported
+//! as an error, together with the line it originated from.
+
+use std::{error::Error, fmt, io::BufRead};
+
+use common::LineStream;
+
+use crate::{priority, shared_items, ItemSet};
+
+/// Reason why a single rucksack is invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidRucksack {
+    /// Items can not be split evenly between both compartments.
+    OddLength { len: usize },
+    /// A byte which is neither `a` through `z` nor `A` through `Z`. Column starts with `1`.
+    InvalidItem { item: u8, column: usize },
+    /// Both compartments have no item in common.
+    NoSharedItem,
+    /// Both compartments have more than one item in common.
+    MultipleSharedItems(ItemSet),
+}
+
+impl fmt::Display for InvalidRucksack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidRucksack::OddLength { len } => {
+                write!(
+                    f,
+                    "odd number of items ({len}) can not be split into compartments"
+                )
+            }
+            InvalidRucksack::InvalidItem { item, column } => {
+                write!(
+                    f,
+                    "invalid item '{}' in column {column}",
+                    item.escape_ascii()
+                )
+            }
+            InvalidRucksack::NoSharedItem => f.write_str("compartments share no item"),
+            InvalidRucksack::MultipleSharedItems(items) => {
+                write!(f, "compartments share {} items: {items}", items.len())
+            }
+        }
+    }
+}
+
+impl Error for InvalidRucksack {}
+
+/// Reason why a group of rucksacks has no badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidGroup {
+    /// No item is carried by every rucksack of the group.
+    NoBadge,
+    /// More than one item is carried by every rucksack of the group.
+    MultipleBadges(ItemSet),
+    /// The input ends before the last group is complete.
+    Incomplete { found: usize, expected: usize },
+}
+
+impl fmt::Display for InvalidGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidGroup::NoBadge => f.write_str("no item is carried by all rucksacks"),
+            InvalidGroup::MultipleBadges(items) => {
+                write!(
+                    f,
+                    "{} items are carried by all rucksacks: {items}",
+                    items.len()
+                )
+            }
+            InvalidGroup::Incomplete { found, expected } => {
+                write!(f, "only {found} of {expected} rucksacks")
+            }
+        }
+    }
+}
+
+impl Error for InvalidGroup {}
+
+/// An invalid rucksack or group, together with the line it originated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationError {
+    Rucksack {
+        /// Line number, starting with `1`.
+        line: usize,
+        reason: InvalidRucksack,
+    },
+    Group {
+        /// Line number of the first rucksack in the group, starting with `1`.
+        line: usize,
+        reason: InvalidGroup,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::Rucksack { line, reason } => {
+                write!(f, "Invalid rucksack in line {line}: {reason}")
+            }
+            ValidationError::Group { line, reason } => {
+                write!(f, "Invalid group starting in line {line}: {reason}")
+            }
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Set of all items in the rucksack. Fails on the first byte which is not an item.
+pub fn checked_items(rucksack: &[u8]) -> Result<ItemSet, InvalidRucksack> {
+    let mut items = ItemSet::EMPTY;
+    for (index, &item) in rucksack.iter().enumerate() {
+        if priority(item) == 0 {
+            return Err(InvalidRucksack::InvalidItem {
+                item,
+                column: index + 1,
+            });
+        }
+        items.insert(item);
+    }
+    Ok(items)
+}
+
+/// The one item both compartments of the rucksack have in common.
+pub fn checked_shared_item(rucksack: &[u8]) -> Result<u8, InvalidRucksack> {
+    if !rucksack.len().is_multiple_of(2) {
+        return Err(InvalidRucksack::OddLength {
+            len: rucksack.len(),
+        });
+    }
+    checked_items(rucksack)?;
+    let shared = shared_items(rucksack);
+    single(shared).ok_or(if shared.is_empty() {
+        InvalidRucksack::NoSharedItem
+    } else {
+        InvalidRucksack::MultipleSharedItems(shared)
+    })
+}
+
+/// The one item shared by both compartments, for each rucksack in the input.
+pub fn checked_shared_items(
+    input: impl BufRead,
+) -> impl Iterator<Item = Result<u8, ValidationError>> {
+    let mut lines = LineStream::new(input);
+    let mut line = 0;
+    std::iter::from_fn(move || {
+        let rucksack = lines.next_line()?;
+        line += 1;
+        Some(
+            checked_shared_item(rucksack)
+                .map_err(|reason| ValidationError::Rucksack { line, reason }),
+        )
+    })
+}
+
+/// The one badge carried by all rucksacks, for each group of `group_size` consecutive rucksacks in
+/// the input. Each rucksack must only contain items, but its compartments are not checked. Panics
+/// if `group_size` is `0`.
+pub fn checked_badges(
+    input: impl BufRead,
+    group_size: usize,
+) -> impl Iterator<Item = Result<u8, ValidationError>> {
+    assert!(group_size > 0, "A group must hold at least one rucksack");
+    let mut lines = LineStream::with_num_lines(input, group_size);
+    let mut first_line = 1;
+    std::iter::from_fn(move || {
+        let rucksacks = lines.next_line()?;
+        let line = first_line;
+        first_line += group_size;
+        let found = rucksacks.split(|&byte| byte == b'\n').count();
+        if found != group_size {
+            return Some(Err(ValidationError::Group {
+                line,
+                reason: InvalidGroup::Incomplete {
+                    found,
+                    expected: group_size,
+                },
+            }));
+        }
+        let mut badges = ItemSet::ALL;
+        for (offset, rucksack) in rucksacks.split(|&byte| byte == b'\n').enumerate() {
+            match checked_items(rucksack) {
+                Ok(items) => badges = badges.intersection(items),
+                Err(reason) => {
+                    return Some(Err(ValidationError::Rucksack {
+                        line: line + offset,
+                        reason,
+                    }))
+                }
+            }
+        }
+        let badge = single(badges).ok_or(ValidationError::Group {
+            line,
+            reason: if badges.is_empty() {
+                InvalidGroup::NoBadge
+            } else {
+                InvalidGroup::MultipleBadges(badges)
+            },
+        });
+        Some(badge)
+    })
+}
+
+/// The only item in the set, if it holds exactly one.
+fn single(items: ItemSet) -> Option<u8> {
+    if items.len() == 1 {
+        items.items().next()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        checked_badges, checked_shared_item, checked_shared_items, InvalidGroup, InvalidRucksack,
+        ValidationError,
+    };
+    use crate::ItemSet;
+
+    #[test]
+    fn invalid_rucksacks() {
+        assert_eq!(Ok(b'p'), checked_shared_item(b"vJrwpWtwJgWrhcsFMMfFFhFp"));
+        assert_eq!(
+            Err(InvalidRucksack::OddLength { len: 3 }),
+            checked_shared_item(b"abc")
+        );
+        assert_eq!(
+            Err(InvalidRucksack::InvalidItem {
+                item: b'1',
+                column: 2
+            }),
+            checked_shared_item(b"a1ab")
+        );
+        assert_eq!(
+            Err(InvalidRucksack::InvalidItem {
+                item: b' ',
+                column: 4
+            }),
+            checked_shared_item(b"abc ")
+        );
+        assert_eq!(
+            Err(InvalidRucksack::NoSharedItem),
+            checked_shared_item(b"abcd")
+        );
+        assert_eq!(
+            Err(InvalidRucksack::MultipleSharedItems(ItemSet::from_items(
+                b"aB"
+            ))),
+            checked_shared_item(b"aBBa")
+        );
+    }
+
+    #[test]
+    fn report_line_of_invalid_rucksack() {
+        let input = "abca\nabcd\naa\n";
+
+        let items: Vec<_> = checked_shared_items(input.as_bytes()).collect();
+
+        assert_eq!(
+            vec![
+                Ok(b'a'),
+                Err(ValidationError::Rucksack {
+                    line: 2,
+                    reason: InvalidRucksack::NoSharedItem
+                }),
+                Ok(b'a'),
+            ],
+            items
+        );
+        assert_eq!(
+            "Invalid rucksack in line 2: compartments share no item",
+            items[1].unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn report_line_of_invalid_group() {
+        let input = "abc\nade\naf\nab\ncd\nef\nxy\nx-y\nxy\nab\nab\nac\n";
+
+        let badges: Vec<_> = checked_badges(input.as_bytes(), 3).collect();
+
+        assert_eq!(
+            vec![
+                Ok(b'a'),
+                Err(ValidationError::Group {
+                    line: 4,
+                    reason: InvalidGroup::NoBadge
+                }),
+                Err(ValidationError::Rucksack {
+                    line: 8,
+                    reason: InvalidRucksack::InvalidItem {
+                        item: b'-',
+                        column: 2
+                    }
+                }),
+                Ok(b'a'),
+            ],
+            badges
+        );
+        assert_eq!(
+            "Invalid group starting in line 1: 2 items are carried by all rucksacks: ab",
+            checked_badges("ab\nab\nab\n".as_bytes(), 3)
+                .next()
+                .unwrap()
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn report_incomplete_last_group() {
+        let input = "ab\nac\nad\nae\naf\n";
+
+        let badges: Vec<_> = checked_badges(input.as_bytes(), 3).collect();
+
+        assert_eq!(
+            vec![
+                Ok(b'a'),
+                Err(ValidationError::Group {
+                    line: 4,
+                    reason: InvalidGroup::Incomplete {
+                        found: 2,
+                        expected: 3
+                    }
+                }),
+            ],
+            badges
+        );
+        assert_eq!(
+            "Invalid group starting in line 4: only 2 of 3 rucksacks",
+            badges[1].unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "A group must hold at least one rucksack")]
+    fn empty_groups() {
+        let _ = checked_badges("ab\n".as_bytes(), 0);
+    }
+}
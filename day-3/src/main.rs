@@ -43,6 +43,7 @@
 //! priorities of those item types?
 
 use anyhow::Error;
+use day_3_common::{checked_shared_items, priority, ValidationError};
 use std::{
     fs::File,
     io::{BufRead, BufReader},
@@ -50,100 +51,20 @@ use std::{
 
 fn main() -> Result<(), Error> {
     let input = BufReader::new(File::open("input.txt")?);
-    let sum = sum_of_priorities(input);
+    let sum = sum_of_priorities(input)?;
     println!("{sum}");
     Ok(())
 }
 
-fn sum_of_priorities(input: impl BufRead) -> u64 {
-    let mut sum = 0;
-    let mut lines = LineStream::new(input);
-    while let Some(rucksack) = lines.next() {
-        sum += priority(common(rucksack)) as u64;
-    }
-    sum
-}
-
-const fn priority(item: u8) -> u8 {
-    match item {
-        b'a'..=b'z' => item - b'a' + 1,
-        b'A'..=b'Z' => item - b'A' + 27,
-        _ => 0,
-    }
-}
-
-/// Item both compartments have in common
-fn common(rucksack: &[u8]) -> u8 {
-    let mid = rucksack.len() / 2;
-    let first_compartment = &rucksack[..mid];
-    let second_compartment = &rucksack[mid..];
-
-    // Since there are only 52 different items in total we use a static array instead of a set.
-    // +1 is needed for the `0` we use to model invalid input and error cases
-    let mut appeared = [false; priority(b'Z') as usize + 1];
-
-    for &item in first_compartment {
-        appeared[priority(item) as usize] = true;
-    }
-
-    second_compartment
-        .iter()
-        .copied()
-        .find(|item| appeared[priority(*item) as usize])
-        .unwrap_or(0)
-}
-
-struct LineStream<R> {
-    input: R,
-    buffer: Vec<u8>,
-}
-
-impl<R> LineStream<R> {
-    fn new(input: R) -> Self {
-        Self {
-            input,
-            buffer: Vec::new(),
-        }
-    }
-
-    fn next(&mut self) -> Option<&'_ [u8]>
-    where
-        R: BufRead,
-    {
-        self.buffer.clear();
-        self.input.read_until(b'\n', &mut self.buffer).ok()?;
-        if self.buffer.is_empty() {
-            None
-        } else {
-            self.buffer.pop();
-            Some(&self.buffer)
-        }
-    }
+fn sum_of_priorities(input: impl BufRead) -> Result<u64, ValidationError> {
+    checked_shared_items(input)
+        .map(|item| item.map(|item| priority(item) as u64))
+        .sum()
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{common, priority, sum_of_priorities};
-
-    #[test]
-    fn common_priority() {
-        assert_eq!(b'p', common(b"vJrwpWtwJgWrhcsFMMfFFhFp"));
-        assert_eq!(b'L', common(b"jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL"));
-        assert_eq!(b'P', common(b"PmmdzqPrVvPwwTWBwg"));
-        assert_eq!(b'v', common(b"wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn"));
-        assert_eq!(b't', common(b"ttgJtRGJQctTZtZT"));
-        assert_eq!(b's', common(b"CrZsJsPPZsGzwwsLwLmpwMDw"));
-    }
-
-    #[test]
-    fn priorities() {
-        assert_eq!(16, priority(b'p'));
-        assert_eq!(38, priority(b'L'));
-        assert_eq!(42, priority(b'P'));
-        assert_eq!(22, priority(b'v'));
-        assert_eq!(20, priority(b't'));
-        assert_eq!(19, priority(b's'));
-    }
+    use crate::sum_of_priorities;
 
     #[test]
     fn rucksack_sum_of_priorities() {
@@ -156,7 +77,7 @@ mod test {
            CrZsJsPPZsGzwwsLwLmpwMDw\n\
         ";
 
-        let actual = sum_of_priorities(input.as_bytes());
+        let actual = sum_of_priorities(input.as_bytes()).unwrap();
 
         assert_eq!(157, actual)
     }
@@ -57,7 +57,7 @@
 //! **In how many assignment pairs does one range fully contain the other?**
 
 use anyhow::Error;
-use atoi::FromRadix10Checked;
+use day_4_common::{assignment_pairs, InvalidPair};
 use std::{
     fs::File,
     io::{BufRead, BufReader},
@@ -65,93 +65,24 @@ use std::{
 
 fn main() -> Result<(), Error> {
     let input = BufReader::new(File::open("input.txt")?);
-    let sum = count_redundant(input);
+    let sum = count_redundant(input)?;
     println!("{sum}");
     Ok(())
 }
 
-fn count_redundant(input: impl BufRead) -> u64 {
+fn count_redundant(input: impl BufRead) -> Result<usize, InvalidPair> {
     let mut count = 0;
-    let mut lines = LineStream::new(input);
-    while let Some(pair) = lines.next().and_then(AssignmentPair::extract_from_bytes) {
-        if pair.redundant() {
-            count += 1
-        }
-    }
-    count
-}
-
-struct AssignmentPair {
-    first: Assignment,
-    second: Assignment,
-}
-
-impl AssignmentPair {
-    fn extract_from_bytes(bytes: &[u8]) -> Option<AssignmentPair> {
-        let (first, taken) = Assignment::extract_from_bytes(bytes)?;
-        let (second, _) = Assignment::extract_from_bytes(&bytes[taken + 1..])?;
-        Some(AssignmentPair { first, second })
-    }
-
-    fn redundant(&self) -> bool {
-        self.first.contains(&self.second) || self.second.contains(&self.first)
-    }
-}
-
-struct Assignment {
-    start: u32,
-    end: u32,
-}
-
-impl Assignment {
-    fn extract_from_bytes(bytes: &[u8]) -> Option<(Assignment, usize)> {
-        let mut offset = 0;
-        let (start, taken) = u32::from_radix_10_checked(&bytes[offset..]);
-        let start = start?;
-        offset += taken + 1; // +1 to account for dash (`-`) in input
-        let (end, taken) = u32::from_radix_10_checked(&bytes[offset..]);
-        let end = end?;
-        offset += taken;
-
-        Some((Assignment { start, end }, offset))
-    }
-
-    fn contains(&self, other: &Assignment) -> bool {
-        self.start <= other.start && self.end >= other.end
-    }
-}
-
-struct LineStream<R> {
-    input: R,
-    buffer: Vec<u8>,
-}
-
-impl<R> LineStream<R> {
-    fn new(input: R) -> Self {
-        Self {
-            input,
-            buffer: Vec::new(),
-        }
-    }
-
-    fn next(&mut self) -> Option<&'_ [u8]>
-    where
-        R: BufRead,
-    {
-        self.buffer.clear();
-        self.input.read_until(b'\n', &mut self.buffer).ok()?;
-        if self.buffer.is_empty() {
-            None
-        } else {
-            self.buffer.pop();
-            Some(&self.buffer)
+    for pair in assignment_pairs(input) {
+        if pair?.redundant() {
+            count += 1;
         }
     }
+    Ok(count)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{count_redundant, AssignmentPair};
+    use crate::count_redundant;
 
     #[test]
     fn count_redundant_pairs() {
@@ -164,19 +95,8 @@ mod tests {
             2-6,4-8\n\
         ";
 
-        let actual = count_redundant(input.as_bytes());
+        let actual = count_redundant(input.as_bytes()).unwrap();
 
         assert_eq!(2, actual)
     }
-
-    #[test]
-    fn assignment_pairs() {
-        let redundant = |bytes| {
-            AssignmentPair::extract_from_bytes(bytes)
-                .unwrap()
-                .redundant()
-        };
-        assert!(!redundant(b"2-4,6-8"));
-        assert!(redundant(b"2-8,3-7"));
-    }
 }
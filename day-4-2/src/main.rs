@@ -73,96 +73,40 @@
 //! **In how many assignment pairs do the ranges overlap?**
 
 use anyhow::Error;
-use atoi::FromRadix10Checked;
+use day_4_common::{assignment_pairs, pair_reports, write_csv, InvalidPair};
 use std::{
+    env,
     fs::File,
-    io::{BufRead, BufReader},
+    io::{BufRead, BufReader, BufWriter},
 };
 
 fn main() -> Result<(), Error> {
     let input = BufReader::new(File::open("input.txt")?);
-    let sum = count_overlapping(input);
+    // Optionally export a report for each pair into a CSV file, for auditing. Invalid lines are
+    // kept in the export, but fail the count just like without an export.
+    let sum = if let Some(path) = env::args().nth(1) {
+        let out = BufWriter::new(File::create(path)?);
+        let summary = write_csv(pair_reports(input), out)?;
+        eprintln!("{summary}");
+        if let Some(invalid) = summary.first_invalid {
+            return Err(invalid.into());
+        }
+        summary.num_overlapping
+    } else {
+        count_overlapping(input)?
+    };
     println!("{sum}");
     Ok(())
 }
 
-fn count_overlapping(input: impl BufRead) -> u64 {
+fn count_overlapping(input: impl BufRead) -> Result<usize, InvalidPair> {
     let mut count = 0;
-    let mut lines = LineStream::new(input);
-    while let Some(pair) = lines.next().and_then(AssignmentPair::extract_from_bytes) {
-        if pair.overlapping() {
-            count += 1
-        }
-    }
-    count
-}
-
-struct AssignmentPair {
-    first: Assignment,
-    second: Assignment,
-}
-
-impl AssignmentPair {
-    fn extract_from_bytes(bytes: &[u8]) -> Option<AssignmentPair> {
-        let (first, taken) = Assignment::extract_from_bytes(bytes)?;
-        let (second, _) = Assignment::extract_from_bytes(&bytes[taken + 1..])?;
-        Some(AssignmentPair { first, second })
-    }
-
-    fn overlapping(&self) -> bool {
-        !(self.first.before(&self.second) || self.second.before(&self.first))
-    }
-}
-
-struct Assignment {
-    start: u32,
-    end: u32,
-}
-
-impl Assignment {
-    fn extract_from_bytes(bytes: &[u8]) -> Option<(Assignment, usize)> {
-        let mut offset = 0;
-        let (start, taken) = u32::from_radix_10_checked(&bytes[offset..]);
-        let start = start?;
-        offset += taken + 1; // +1 to account for dash (`-`) in input
-        let (end, taken) = u32::from_radix_10_checked(&bytes[offset..]);
-        let end = end?;
-        offset += taken;
-
-        Some((Assignment { start, end }, offset))
-    }
-
-    fn before(&self, other: &Assignment) -> bool {
-        self.end < other.start
-    }
-}
-
-struct LineStream<R> {
-    input: R,
-    buffer: Vec<u8>,
-}
-
-impl<R> LineStream<R> {
-    fn new(input: R) -> Self {
-        Self {
-            input,
-            buffer: Vec::new(),
-        }
-    }
-
-    fn next(&mut self) -> Option<&'_ [u8]>
-    where
-        R: BufRead,
-    {
-        self.buffer.clear();
-        self.input.read_until(b'\n', &mut self.buffer).ok()?;
-        if self.buffer.is_empty() {
-            None
-        } else {
-            self.buffer.pop();
-            Some(&self.buffer)
+    for pair in assignment_pairs(input) {
+        if pair?.overlapping() {
+            count += 1;
         }
     }
+    Ok(count)
 }
 
 #[cfg(test)]
@@ -180,7 +124,7 @@ mod tests {
             2-6,4-8\n\
         ";
 
-        let actual = count_overlapping(input.as_bytes());
+        let actual = count_overlapping(input.as_bytes()).unwrap();
 
         assert_eq!(4, actual)
     }
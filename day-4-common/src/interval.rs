@@ -0,0 +1,202 @@
+//! Ranges of section IDs and the usual set operations on them.
+
+use std::fmt;
+
+use atoi::FromRadix10Checked;
+
+/// Sections `start` to `end`, both inclusive. Never empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    start: u32,
+    end: u32,
+}
+
+impl Interval {
+    /// # Panics
+    ///
+    /// If `start` is greater than `end`
+    pub fn new(start: u32, end: u32) -> Self {
+        assert!(start <= end, "Interval {start}-{end} must not be empty");
+        Self { start, end }
+    }
+
+    /// Parses an interval like `2-4` at the beginning of `bytes`. Returns the interval and the
+    /// number of bytes it took up.
+    pub fn extract_from_bytes(bytes: &[u8]) -> Option<(Interval, usize)> {
+        let mut offset = 0;
+        let (start, taken) = u32::from_radix_10_checked(&bytes[offset..]);
+        let start = start.filter(|_| taken != 0)?;
+        offset += taken;
+        if bytes.get(offset) != Some(&b'-') {
+            return None;
+        }
+        offset += 1;
+        let (end, taken) = u32::from_radix_10_checked(&bytes[offset..]);
+        let end = end.filter(|_| taken != 0)?;
+        offset += taken;
+        if start > end {
+            return None;
+        }
+
+        Some((Interval { start, end }, offset))
+    }
+
+    /// First section
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// Last section
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// Number of sections
+    pub fn len(&self) -> u64 {
+        u64::from(self.end - self.start) + 1
+    }
+
+    /// Always `false`, intervals contain at least one section.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    /// `true` if all sections of `self` come before the first one of `other`.
+    pub fn before(&self, other: &Interval) -> bool {
+        self.end < other.start
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        !(self.before(other) || other.before(self))
+    }
+
+    /// Sections in both intervals.
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        self.overlaps(other).then(|| Interval {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        })
+    }
+
+    /// Sections in either interval. `None` if there are sections in between, which are in neither
+    /// of them, since the union would not be an interval then.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        (self.overlaps(other) || self.adjacent(other)).then(|| Interval {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+
+    /// Sections in `self`, but not in `other`. These are up to two intervals, one before and one
+    /// after `other`.
+    pub fn difference(&self, other: &Interval) -> impl Iterator<Item = Interval> {
+        let before = (self.start < other.start).then(|| Interval {
+            start: self.start,
+            end: self.end.min(other.start - 1),
+        });
+        let after = (self.end > other.end).then(|| Interval {
+            start: self.start.max(other.end + 1),
+            end: self.end,
+        });
+        before.into_iter().chain(after)
+    }
+
+    /// `true` if one interval ends right before the other one starts.
+    fn adjacent(&self, other: &Interval) -> bool {
+        self.end.checked_add(1) == Some(other.start) || other.end.checked_add(1) == Some(self.start)
+    }
+}
+
+/// Same format as in the puzzle input, e.g. `2-4`.
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Union of all intervals, as sorted list of disjoint intervals. Overlapping and adjacent intervals
+/// are merged into one.
+pub fn merge(intervals: impl IntoIterator<Item = Interval>) -> Vec<Interval> {
+    let mut intervals: Vec<Interval> = intervals.into_iter().collect();
+    intervals.sort_unstable();
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        match merged.last_mut().and_then(|last| last.union(&interval)) {
+            Some(union) => *merged.last_mut().unwrap() = union,
+            None => merged.push(interval),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge, Interval};
+
+    fn interval(start: u32, end: u32) -> Interval {
+        Interval::new(start, end)
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Some((interval(2, 4), 3)),
+            Interval::extract_from_bytes(b"2-4,6-8")
+        );
+        assert_eq!(
+            Some((interval(12, 140), 6)),
+            Interval::extract_from_bytes(b"12-140")
+        );
+        assert_eq!(None, Interval::extract_from_bytes(b"4-2"));
+        assert_eq!(None, Interval::extract_from_bytes(b"4,2"));
+        assert_eq!(None, Interval::extract_from_bytes(b"-2"));
+        assert_eq!(None, Interval::extract_from_bytes(b"2-"));
+        assert_eq!("6-8", interval(6, 8).to_string());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = interval(2, 6);
+        let b = interval(4, 8);
+        let c = interval(7, 9);
+
+        assert_eq!(5, a.len());
+        assert_eq!(Some(interval(4, 6)), a.intersection(&b));
+        assert_eq!(None, a.intersection(&c));
+        assert_eq!(Some(interval(2, 8)), a.union(&b));
+        assert_eq!(Some(interval(2, 9)), a.union(&c));
+        assert_eq!(None, interval(2, 3).union(&interval(5, 6)));
+        assert_eq!(vec![interval(2, 3)], a.difference(&b).collect::<Vec<_>>());
+        assert_eq!(
+            vec![interval(2, 3), interval(7, 8)],
+            interval(2, 8)
+                .difference(&interval(4, 6))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(0, b.difference(&interval(1, 9)).count());
+        assert_eq!(vec![a], a.difference(&c).collect::<Vec<_>>());
+        assert!(interval(2, 8).contains(&interval(3, 7)));
+        assert!(interval(5, 7).overlaps(&interval(7, 9)));
+        assert!(interval(2, 4).before(&interval(6, 8)));
+    }
+
+    #[test]
+    fn merge_intervals() {
+        let merged = merge([
+            interval(6, 8),
+            interval(2, 3),
+            interval(4, 4),
+            interval(11, 12),
+            interval(7, 9),
+        ]);
+
+        assert_eq!(
+            vec![interval(2, 4), interval(6, 9), interval(11, 12)],
+            merged
+        );
+    }
+}
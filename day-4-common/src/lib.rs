@@ -0,0 +1,133 @@
+//! Section assignments of Day 4: Camp Cleanup. Each line of the input assigns a range of sections
+//! to each of two elves. Part one counts the pairs in which one assignment fully contains the
+//! other, part two the pairs which overlap at all.
+
+use std::{error::Error, fmt, io::BufRead};
+
+use common::InputIterator;
+
+pub use self::{
+    interval::{merge, Interval},
+    report::{pair_reports, write_csv, Containment, PairReport, Summary},
+    sweep::{coverage, covered_by_exactly, uncovered},
+};
+
+mod interval;
+mod report;
+mod sweep;
+
+/// Assignments of the two elves forming a pair, e.g. `2-4,6-8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssignmentPair {
+    pub first: Interval,
+    pub second: Interval,
+}
+
+impl AssignmentPair {
+    pub fn extract_from_bytes(bytes: &[u8]) -> Option<AssignmentPair> {
+        let (first, taken) = Interval::extract_from_bytes(bytes)?;
+        if bytes.get(taken) != Some(&b',') {
+            return None;
+        }
+        let (second, _) = Interval::extract_from_bytes(&bytes[taken + 1..])?;
+        Some(AssignmentPair { first, second })
+    }
+
+    /// `true` if one assignment fully contains the other.
+    pub fn redundant(&self) -> bool {
+        self.first.contains(&self.second) || self.second.contains(&self.first)
+    }
+
+    pub fn overlapping(&self) -> bool {
+        self.first.overlaps(&self.second)
+    }
+}
+
+/// A line which is not a valid assignment pair, e.g. because a range ends before it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidPair {
+    /// Line number, starting with `1`.
+    pub line: usize,
+}
+
+impl fmt::Display for InvalidPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid assignment pair in line {}", self.line)
+    }
+}
+
+impl Error for InvalidPair {}
+
+/// Assignment pairs in the input, or the line number for each line which is not a valid pair. See
+/// [`pair_reports`] for the details of each pair.
+pub fn assignment_pairs(
+    input: impl BufRead,
+) -> impl Iterator<Item = Result<AssignmentPair, InvalidPair>> {
+    InputIterator::new(input, AssignmentPair::extract_from_bytes)
+        .enumerate()
+        .map(|(index, pair)| pair.ok_or(InvalidPair { line: index + 1 }))
+}
+
+/// Every single assignment in the input, both of the first and the second elf of each pair. Fails
+/// on the first line which is not a valid pair.
+pub fn assignments(input: impl BufRead) -> Result<Vec<Interval>, InvalidPair> {
+    let mut assignments = Vec::new();
+    for pair in assignment_pairs(input) {
+        let pair = pair?;
+        assignments.extend([pair.first, pair.second]);
+    }
+    Ok(assignments)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assignment_pairs, assignments, covered_by_exactly, uncovered, AssignmentPair, Interval,
+        InvalidPair,
+    };
+
+    const INPUT: &str = "\
+        2-4,6-8\n\
+        2-3,4-5\n\
+        5-7,7-9\n\
+        2-8,3-7\n\
+        6-6,4-6\n\
+        2-6,4-8\n\
+    ";
+
+    #[test]
+    fn parse_assignment_pairs() {
+        let pair = |bytes| AssignmentPair::extract_from_bytes(bytes).unwrap();
+
+        assert!(!pair(b"2-4,6-8").redundant());
+        assert!(pair(b"2-8,3-7").redundant());
+        assert!(!pair(b"2-3,4-5").overlapping());
+        assert!(pair(b"5-7,7-9").overlapping());
+        assert!(AssignmentPair::extract_from_bytes(b"2-4;6-8").is_none());
+    }
+
+    #[test]
+    fn queries_over_all_assignments() {
+        let assignments = assignments(INPUT.as_bytes()).unwrap();
+        let only_one_elf = covered_by_exactly(assignments.iter().copied(), 1);
+        let nobody = uncovered(assignments, Interval::new(1, 10));
+
+        assert_eq!(vec![Interval::new(9, 9)], only_one_elf);
+        assert_eq!(vec![Interval::new(1, 1), Interval::new(10, 10)], nobody);
+    }
+
+    #[test]
+    fn report_line_of_reversed_range() {
+        let input = "2-4,6-8\n4-2,1-9\n2-8,3-7\n";
+
+        let pairs: Vec<_> = assignment_pairs(input.as_bytes()).collect();
+
+        assert_eq!(3, pairs.len());
+        assert_eq!(Err(InvalidPair { line: 2 }), pairs[1]);
+        assert!(pairs[2].unwrap().redundant());
+        assert_eq!(
+            "Invalid assignment pair in line 2",
+            assignments(input.as_bytes()).unwrap_err().to_string()
+        );
+    }
+}
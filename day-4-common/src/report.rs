@@ -0,0 +1,236 @@
+//! Per pair results, so the cleanup assignments can be audited pair by pair rather than by a
+//! single count.
+
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+};
+
+use crate::{assignment_pairs, AssignmentPair, Interval, InvalidPair};
+
+/// Whether one assignment of a pair fully contains the other one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Containment {
+    Neither,
+    FirstContainsSecond,
+    SecondContainsFirst,
+    /// Both elves are assigned the same sections.
+    Identical,
+}
+
+impl Containment {
+    /// Name as used in CSV exports.
+    pub fn name(self) -> &'static str {
+        match self {
+            Containment::Neither => "neither",
+            Containment::FirstContainsSecond => "first_contains_second",
+            Containment::SecondContainsFirst => "second_contains_first",
+            Containment::Identical => "identical",
+        }
+    }
+}
+
+/// Result for a single pair of elves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PairReport {
+    /// Line of the pair in the input, starting at `1`
+    pub line: usize,
+    pub pair: AssignmentPair,
+    /// Sections assigned to both elves
+    pub overlap: Option<Interval>,
+    pub containment: Containment,
+}
+
+impl PairReport {
+    pub fn new(line: usize, pair: AssignmentPair) -> Self {
+        let AssignmentPair { first, second } = pair;
+        let containment = match (first.contains(&second), second.contains(&first)) {
+            (true, true) => Containment::Identical,
+            (true, false) => Containment::FirstContainsSecond,
+            (false, true) => Containment::SecondContainsFirst,
+            (false, false) => Containment::Neither,
+        };
+        Self {
+            line,
+            pair,
+            overlap: first.intersection(&second),
+            containment,
+        }
+    }
+}
+
+/// Report for each pair in the input, or the line number for each line which is not a valid pair.
+pub fn pair_reports(input: impl BufRead) -> impl Iterator<Item = Result<PairReport, InvalidPair>> {
+    assignment_pairs(input)
+        .enumerate()
+        .map(|(index, pair)| pair.map(|pair| PairReport::new(index + 1, pair)))
+}
+
+/// Totals over all pairs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub num_pairs: usize,
+    /// Pairs sharing at least one section (answer to part two)
+    pub num_overlapping: usize,
+    /// Pairs in which one assignment fully contains the other (answer to part one)
+    pub num_redundant: usize,
+    /// Sum of the lengths of all overlaps
+    pub overlapping_sections: u64,
+    /// Lines which are not a valid pair and therefore not part of any other count
+    pub num_invalid: usize,
+    /// First line which is not a valid pair, i.e. the error reported if the pairs are not exported
+    pub first_invalid: Option<InvalidPair>,
+}
+
+impl Summary {
+    pub fn add(&mut self, report: &PairReport) {
+        self.num_pairs += 1;
+        if let Some(overlap) = report.overlap {
+            self.num_overlapping += 1;
+            self.overlapping_sections += overlap.len();
+        }
+        if report.containment != Containment::Neither {
+            self.num_redundant += 1;
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} pairs, {} overlapping by {} sections in total, {} fully contained",
+            self.num_pairs, self.num_overlapping, self.overlapping_sections, self.num_redundant
+        )?;
+        if self.num_invalid != 0 {
+            write!(f, ", {} invalid", self.num_invalid)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes one line per pair as comma separated values, preceded by a header. Lines which are not a
+/// valid pair are kept as `invalid` rows, so they show up in the audit. Returns the totals over all
+/// pairs.
+pub fn write_csv(
+    reports: impl IntoIterator<Item = Result<PairReport, InvalidPair>>,
+    mut out: impl Write,
+) -> io::Result<Summary> {
+    writeln!(out, "line,first,second,overlap,overlap_length,containment")?;
+    let mut summary = Summary::default();
+    for report in reports {
+        let report = match report {
+            Ok(report) => report,
+            Err(invalid) => {
+                writeln!(out, "{},,,,,invalid", invalid.line)?;
+                summary.num_invalid += 1;
+                summary.first_invalid.get_or_insert(invalid);
+                continue;
+            }
+        };
+        let (overlap, overlap_len) = match report.overlap {
+            Some(overlap) => (overlap.to_string(), overlap.len()),
+            None => (String::new(), 0),
+        };
+        writeln!(
+            out,
+            "{},{},{},{overlap},{overlap_len},{}",
+            report.line,
+            report.pair.first,
+            report.pair.second,
+            report.containment.name()
+        )?;
+        summary.add(&report);
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Interval, InvalidPair};
+
+    use super::{pair_reports, write_csv, Containment, Summary};
+
+    const INPUT: &str = "\
+        2-4,6-8\n\
+        2-3,4-5\n\
+        5-7,7-9\n\
+        2-8,3-7\n\
+        6-6,4-6\n\
+        2-6,4-8\n\
+        3-5,3-5\n\
+    ";
+
+    #[test]
+    fn report_each_pair() {
+        let reports: Vec<_> = pair_reports(INPUT.as_bytes()).map(Result::unwrap).collect();
+
+        assert_eq!(7, reports.len());
+        assert_eq!(None, reports[0].overlap);
+        assert_eq!(Containment::Neither, reports[0].containment);
+        assert_eq!(3, reports[2].line);
+        assert_eq!(Some(Interval::new(7, 7)), reports[2].overlap);
+        assert_eq!(Containment::FirstContainsSecond, reports[3].containment);
+        assert_eq!(Containment::SecondContainsFirst, reports[4].containment);
+        assert_eq!(Containment::Identical, reports[6].containment);
+    }
+
+    #[test]
+    fn export_csv_and_summary() {
+        let mut csv = Vec::new();
+
+        let summary = write_csv(pair_reports(INPUT.as_bytes()), &mut csv).unwrap();
+
+        let expected = "\
+            line,first,second,overlap,overlap_length,containment\n\
+            1,2-4,6-8,,0,neither\n\
+            2,2-3,4-5,,0,neither\n\
+            3,5-7,7-9,7-7,1,neither\n\
+            4,2-8,3-7,3-7,5,first_contains_second\n\
+            5,6-6,4-6,6-6,1,second_contains_first\n\
+            6,2-6,4-8,4-6,3,neither\n\
+            7,3-5,3-5,3-5,3,identical\n\
+        ";
+        assert_eq!(expected, String::from_utf8(csv).unwrap());
+        assert_eq!(
+            Summary {
+                num_pairs: 7,
+                num_overlapping: 5,
+                num_redundant: 3,
+                overlapping_sections: 13,
+                num_invalid: 0,
+                first_invalid: None,
+            },
+            summary
+        );
+        assert_eq!(
+            "7 pairs, 5 overlapping by 13 sections in total, 3 fully contained",
+            summary.to_string()
+        );
+    }
+
+    #[test]
+    fn keep_invalid_lines_in_export() {
+        let input = "2-4,6-8\n4-2,1-9\n2-8,3-7\n";
+        let mut csv = Vec::new();
+
+        let reports: Vec<_> = pair_reports(input.as_bytes()).collect();
+        let summary = write_csv(reports.clone(), &mut csv).unwrap();
+
+        assert_eq!(3, reports.len());
+        assert_eq!(Err(InvalidPair { line: 2 }), reports[1]);
+        assert_eq!(3, reports[2].unwrap().line);
+        let expected = "\
+            line,first,second,overlap,overlap_length,containment\n\
+            1,2-4,6-8,,0,neither\n\
+            2,,,,,invalid\n\
+            3,2-8,3-7,3-7,5,first_contains_second\n\
+        ";
+        assert_eq!(expected, String::from_utf8(csv).unwrap());
+        assert_eq!(Some(InvalidPair { line: 2 }), summary.first_invalid);
+        assert_eq!(
+            "2 pairs, 1 overlapping by 5 sections in total, 1 fully contained, 1 invalid",
+            summary.to_string()
+        );
+    }
+}
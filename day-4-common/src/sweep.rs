@@ -0,0 +1,166 @@
+//! Coverage of sections by many intervals at once. Rather than counting for every section how many
+//! intervals contain it, we sweep over the starts and ends of all intervals in order and keep track
+//! of the number of intervals currently open.
+
+use crate::{merge, Interval};
+
+/// Splits the sections from the first start to the last end of all intervals into consecutive
+/// pieces, each of which is covered by the same number of intervals throughout. Neighboring pieces
+/// differ in their number of intervals.
+pub fn coverage(intervals: impl IntoIterator<Item = Interval>) -> Vec<(Interval, usize)> {
+    // Number of intervals opening (`+1`) or closing (`-1`) at each boundary. An interval closes
+    // right after its last section. Boundaries are `u64`, so an interval may end in `u32::MAX`.
+    let mut events: Vec<(u64, isize)> = intervals
+        .into_iter()
+        .flat_map(|interval| {
+            [
+                (u64::from(interval.start()), 1),
+                (u64::from(interval.end()) + 1, -1),
+            ]
+        })
+        .collect();
+    events.sort_unstable();
+
+    let mut pieces: Vec<(Interval, usize)> = Vec::new();
+    let mut open: usize = 0;
+    for (index, &(boundary, delta)) in events.iter().enumerate() {
+        open = open.checked_add_signed(delta).unwrap();
+        let next = match events.get(index + 1) {
+            Some(&(next, _)) if next > boundary => next,
+            // More events at the same boundary, or the last interval closed
+            _ => continue,
+        };
+        let piece = Interval::new(boundary as u32, (next - 1) as u32);
+        match pieces.last_mut() {
+            Some((last, count)) if *count == open => *last = last.union(&piece).unwrap(),
+            _ => pieces.push((piece, open)),
+        }
+    }
+    pieces
+}
+
+/// Sections covered by exactly `k` intervals. With `k` zero, these are the gaps between the
+/// intervals.
+pub fn covered_by_exactly(
+    intervals: impl IntoIterator<Item = Interval>,
+    k: usize,
+) -> Vec<Interval> {
+    coverage(intervals)
+        .into_iter()
+        .filter(|&(_, count)| count == k)
+        .map(|(piece, _)| piece)
+        .collect()
+}
+
+/// Sections within `range`, which are not covered by any interval.
+pub fn uncovered(intervals: impl IntoIterator<Item = Interval>, range: Interval) -> Vec<Interval> {
+    let mut remaining = vec![range];
+    for covered in merge(intervals) {
+        remaining = remaining
+            .iter()
+            .flat_map(|piece| piece.difference(&covered))
+            .collect();
+    }
+    remaining
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interval;
+
+    use super::{coverage, covered_by_exactly, uncovered};
+
+    fn interval(start: u32, end: u32) -> Interval {
+        Interval::new(start, end)
+    }
+
+    /// All assignments of the example
+    fn example() -> Vec<Interval> {
+        [
+            (2, 4),
+            (6, 8),
+            (2, 3),
+            (4, 5),
+            (5, 7),
+            (7, 9),
+            (2, 8),
+            (3, 7),
+            (6, 6),
+            (4, 6),
+            (2, 6),
+            (4, 8),
+        ]
+        .into_iter()
+        .map(|(start, end)| interval(start, end))
+        .collect()
+    }
+
+    /// Counts for every section how many intervals contain it
+    fn naive_count(intervals: &[Interval], section: u32) -> usize {
+        intervals
+            .iter()
+            .filter(|interval| interval.contains(&Interval::new(section, section)))
+            .count()
+    }
+
+    #[test]
+    fn coverage_matches_counting_each_section() {
+        let intervals = example();
+
+        let pieces = coverage(intervals.iter().copied());
+
+        assert_eq!(2, pieces.first().unwrap().0.start());
+        assert_eq!(9, pieces.last().unwrap().0.end());
+        for (piece, count) in &pieces {
+            for section in piece.start()..=piece.end() {
+                assert_eq!(naive_count(&intervals, section), *count);
+            }
+        }
+        // Pieces are consecutive and differ from their neighbors
+        for pair in pieces.windows(2) {
+            assert_eq!(pair[0].0.end() + 1, pair[1].0.start());
+            assert_ne!(pair[0].1, pair[1].1);
+        }
+    }
+
+    #[test]
+    fn exactly_k() {
+        let intervals = [interval(1, 4), interval(3, 6), interval(10, 12)];
+
+        assert_eq!(
+            vec![interval(1, 2), interval(5, 6), interval(10, 12)],
+            covered_by_exactly(intervals, 1)
+        );
+        assert_eq!(vec![interval(3, 4)], covered_by_exactly(intervals, 2));
+        assert_eq!(vec![interval(7, 9)], covered_by_exactly(intervals, 0));
+        assert!(covered_by_exactly(intervals, 3).is_empty());
+    }
+
+    #[test]
+    fn nobody_covers() {
+        let intervals = [interval(3, 4), interval(6, 8), interval(7, 9)];
+
+        assert_eq!(
+            vec![interval(1, 2), interval(5, 5), interval(10, 12)],
+            uncovered(intervals, interval(1, 12))
+        );
+        assert_eq!(vec![interval(5, 5)], uncovered(intervals, interval(4, 6)));
+        assert!(uncovered(intervals, interval(6, 9)).is_empty());
+    }
+
+    #[test]
+    fn interval_ending_in_last_section() {
+        let pieces = coverage([
+            interval(u32::MAX - 1, u32::MAX),
+            interval(u32::MAX, u32::MAX),
+        ]);
+
+        assert_eq!(
+            vec![
+                (interval(u32::MAX - 1, u32::MAX - 1), 1),
+                (interval(u32::MAX, u32::MAX), 2)
+            ],
+            pieces
+        );
+    }
+}
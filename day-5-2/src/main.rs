@@ -138,115 +138,16 @@
 //!
 //! **After the rearrangement procedure completes, what crate ends up on top of each stack?**
 
-use std::{
-    cmp::max,
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
 use anyhow::Error;
-use atoi::FromRadix10Checked;
-use lines::LineStream;
-
-mod lines;
+use day_5_common::{run, CraneModel};
 
 fn main() -> Result<(), Error> {
-    let input = BufReader::new(File::open("input.txt")?);
-    let message = top_crates(input);
-    println!("{message}");
-    Ok(())
-}
-
-fn top_crates(input: impl BufRead) -> String {
-    let mut lines = LineStream::new(input);
-    let mut crates = Crates::from_lines(&mut lines);
-    lines.next(); // Jump over empty line
-
-    while let Some(line) = lines.next() {
-        let instruction = Instruction::from_line(line);
-        crates.apply(&instruction);
-        eprintln!("{}", crates.top_crates());
-    }
-
-    crates.top_crates()
-}
-
-struct Crates {
-    buffer: Vec<char>,
-    // Stacks with crates in bottom up order (the lowest crate is first).
-    stacks: Vec<Vec<char>>,
-}
-
-impl Crates {
-    fn from_lines(lines: &mut LineStream<impl BufRead>) -> Self {
-        let mut stacks: Vec<Vec<char>> = Vec::new();
-        while let Some(line) = lines.next() {
-            if !line.contains(&b'[') {
-                break;
-            }
-            for (stack_index, byte_index) in (1..line.len()).step_by(4).enumerate() {
-                match line[byte_index] {
-                    b' ' => (),
-                    item => {
-                        stacks.resize_with(max(stacks.len(), stack_index + 1), Vec::new);
-                        stacks[stack_index].push(item as char)
-                    }
-                }
-            }
-        }
-
-        for stack in &mut stacks {
-            stack.reverse();
-        }
-
-        Self {
-            buffer: Vec::new(),
-            stacks,
-        }
-    }
-
-    fn apply(&mut self, instruction: &Instruction) {
-        self.buffer.clear();
-        let from = &mut self.stacks[instruction.from];
-        let at = from.len() - instruction.amount;
-        self.buffer.extend_from_slice(&from[at..]);
-        from.resize(at, '\0');
-        let to = &mut self.stacks[instruction.to];
-        to.extend_from_slice(&self.buffer);
-    }
-
-    fn top_crates(&self) -> String {
-        self.stacks
-            .iter()
-            .map(|stack| stack.last().copied().unwrap_or_default())
-            .collect()
-    }
-}
-
-struct Instruction {
-    /// Number of crates to move
-    amount: usize,
-    from: usize,
-    to: usize,
-}
-
-impl Instruction {
-    fn from_line(line: &[u8]) -> Instruction {
-        let (amount, digits_amount) = usize::from_radix_10_checked(&line[5..]);
-        let (from, digits_from) = usize::from_radix_10_checked(&line[(5 + 6 + digits_amount)..]);
-        let (to, _) =
-            usize::from_radix_10_checked(&line[(5 + 6 + 4 + digits_amount + digits_from)..]);
-        Instruction {
-            amount: amount.unwrap(),
-            from: from.unwrap() - 1,
-            to: to.unwrap() - 1,
-        }
-    }
+    Ok(run(CraneModel::CrateMover9001)?)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::top_crates;
+    use day_5_common::{rearrange, CrateMover9001};
 
     #[test]
     fn top_crates_example_given() {
@@ -262,7 +163,9 @@ mod tests {
             move 1 from 1 to 2\n\
         ";
 
-        let actual = top_crates(input.as_bytes());
+        let actual = rearrange(input.as_bytes(), &CrateMover9001, None)
+            .unwrap()
+            .top_crates();
 
         assert_eq!("MCD", actual);
     }
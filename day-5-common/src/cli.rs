@@ -0,0 +1,124 @@
+//! Command line shared by both parts. They only differ in the crane used by default.
+
+use std::{
+    env,
+    error::Error,
+    fmt,
+    fs::File,
+    io::{self, stderr, BufRead, BufReader, Write},
+};
+
+use crate::{rearrange, CraneModel, RearrangeError, UnknownCrane};
+
+/// Reason why [`run`] failed.
+#[derive(Debug)]
+pub enum RunError {
+    UnknownCrane(UnknownCrane),
+    /// `input.txt` could not be opened.
+    Input(io::Error),
+    Rearrange(RearrangeError),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::UnknownCrane(error) => error.fmt(f),
+            RunError::Input(_) => f.write_str("Can not open input file"),
+            RunError::Rearrange(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for RunError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        // Other variants display the error they wrap, so they share its source
+        match self {
+            RunError::UnknownCrane(error) => error.source(),
+            RunError::Input(error) => Some(error),
+            RunError::Rearrange(error) => error.source(),
+        }
+    }
+}
+
+impl From<UnknownCrane> for RunError {
+    fn from(error: UnknownCrane) -> Self {
+        RunError::UnknownCrane(error)
+    }
+}
+
+impl From<RearrangeError> for RunError {
+    fn from(error: RearrangeError) -> Self {
+        RunError::Rearrange(error)
+    }
+}
+
+/// Rearranges the crates in `input.txt` and prints the top crates.
+///
+/// ```text
+/// day-5-<part> [--trace] [9000|9001|limited:<capacity>]
+/// ```
+///
+/// `--trace` draws the stacks after each step to stderr. The crane model defaults to `default`.
+pub fn run(default: CraneModel) -> Result<(), RunError> {
+    let (crane, trace) = parse_args(env::args().skip(1), default)?;
+    let input = BufReader::new(File::open("input.txt").map_err(RunError::Input)?);
+    let message = if trace {
+        top_crates(input, &crane, Some(&mut stderr().lock()))?
+    } else {
+        top_crates(input, &crane, None)?
+    };
+    println!("{message}");
+    Ok(())
+}
+
+/// Crane model and whether to trace the rearrangement.
+fn parse_args(
+    args: impl IntoIterator<Item = String>,
+    default: CraneModel,
+) -> Result<(CraneModel, bool), UnknownCrane> {
+    let mut crane = default;
+    let mut trace = false;
+    for arg in args {
+        match arg.as_str() {
+            "--trace" => trace = true,
+            name => crane = name.parse()?,
+        }
+    }
+    Ok((crane, trace))
+}
+
+fn top_crates(
+    input: impl BufRead,
+    crane: &CraneModel,
+    trace: Option<&mut dyn Write>,
+) -> Result<String, RearrangeError> {
+    Ok(rearrange(input, crane, trace)?.top_crates())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CraneModel, UnknownCrane};
+
+    use super::parse_args;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|&arg| arg.to_owned()).collect()
+    }
+
+    #[test]
+    fn crane_and_trace_from_args() {
+        let (crane, trace) = parse_args(args(&[]), CraneModel::CrateMover9001).unwrap();
+        assert!(matches!(crane, CraneModel::CrateMover9001));
+        assert!(!trace);
+
+        let (crane, trace) =
+            parse_args(args(&["9000", "--trace"]), CraneModel::CrateMover9001).unwrap();
+        assert!(matches!(crane, CraneModel::CrateMover9000));
+        assert!(trace);
+
+        assert_eq!(
+            UnknownCrane("9002".to_owned()),
+            parse_args(args(&["9002"]), CraneModel::CrateMover9000).unwrap_err()
+        );
+    }
+}
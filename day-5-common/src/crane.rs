@@ -0,0 +1,156 @@
+//! The cranes moving the crates. They only differ in how many crates they can lift at once.
+
+use std::{error::Error, fmt, str::FromStr};
+
+/// Moves crates from the top of one stack onto the top of another.
+pub trait Crane {
+    /// Moves the top `amount` crates of `from` onto `to`. Stacks are in bottom up order. `from`
+    /// holds at least `amount` crates.
+    fn move_crates(&self, amount: usize, from: &mut Vec<String>, to: &mut Vec<String>);
+}
+
+/// The CrateMover 9000 of part one lifts a single crate at a time, so the moved crates end up in
+/// reverse order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn move_crates(&self, amount: usize, from: &mut Vec<String>, to: &mut Vec<String>) {
+        let at = from.len() - amount;
+        to.extend(from.drain(at..).rev());
+    }
+}
+
+/// The CrateMover 9001 of part two lifts all crates at once, so they keep their order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn move_crates(&self, amount: usize, from: &mut Vec<String>, to: &mut Vec<String>) {
+        let at = from.len() - amount;
+        to.extend(from.drain(at..));
+    }
+}
+
+/// Lifts up to `capacity` crates at once. Larger moves are split into several lifts, each of which
+/// keeps the order of its crates. With a capacity of `1` this behaves like the
+/// [`CrateMover9000`], with an unlimited one like the [`CrateMover9001`].
+#[derive(Debug, Clone, Copy)]
+pub struct LimitedCrane {
+    capacity: usize,
+}
+
+impl LimitedCrane {
+    /// # Panics
+    ///
+    /// If `capacity` is zero
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity != 0,
+            "Crane must be able to lift at least one crate"
+        );
+        Self { capacity }
+    }
+}
+
+impl Crane for LimitedCrane {
+    fn move_crates(&self, amount: usize, from: &mut Vec<String>, to: &mut Vec<String>) {
+        let mut remaining = amount;
+        while remaining != 0 {
+            let lift = remaining.min(self.capacity);
+            let at = from.len() - lift;
+            to.extend(from.drain(at..));
+            remaining -= lift;
+        }
+    }
+}
+
+/// Crane selected by name at runtime, e.g. from the command line. Parses `9000`, `9001` and
+/// `limited:<capacity>`.
+#[derive(Debug, Clone, Copy)]
+pub enum CraneModel {
+    CrateMover9000,
+    CrateMover9001,
+    Limited(LimitedCrane),
+}
+
+impl Crane for CraneModel {
+    fn move_crates(&self, amount: usize, from: &mut Vec<String>, to: &mut Vec<String>) {
+        match self {
+            CraneModel::CrateMover9000 => CrateMover9000.move_crates(amount, from, to),
+            CraneModel::CrateMover9001 => CrateMover9001.move_crates(amount, from, to),
+            CraneModel::Limited(crane) => crane.move_crates(amount, from, to),
+        }
+    }
+}
+
+impl FromStr for CraneModel {
+    type Err = UnknownCrane;
+
+    fn from_str(name: &str) -> Result<Self, UnknownCrane> {
+        match name {
+            "9000" => Ok(CraneModel::CrateMover9000),
+            "9001" => Ok(CraneModel::CrateMover9001),
+            _ => name
+                .strip_prefix("limited:")
+                .and_then(|capacity| capacity.parse().ok())
+                .filter(|&capacity| capacity != 0)
+                .map(|capacity| CraneModel::Limited(LimitedCrane::new(capacity)))
+                .ok_or_else(|| UnknownCrane(name.to_owned())),
+        }
+    }
+}
+
+/// Name which does not denote any [`CraneModel`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCrane(pub String);
+
+impl fmt::Display for UnknownCrane {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Unknown crane '{}'. Expected '9000', '9001' or 'limited:<capacity>'.",
+            self.0
+        )
+    }
+}
+
+impl Error for UnknownCrane {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Crane, CraneModel, CrateMover9000, CrateMover9001, LimitedCrane};
+
+    /// Moves four crates off a stack of five. Returns the labels of the stack they are moved onto.
+    fn move_four(crane: &impl Crane) -> String {
+        let mut from = ["A", "B", "C", "D", "E"].map(String::from).to_vec();
+        let mut to = vec!["X".to_owned()];
+        crane.move_crates(4, &mut from, &mut to);
+        assert_eq!(vec!["A"], from);
+        to.concat()
+    }
+
+    #[test]
+    fn crane_models() {
+        assert_eq!("XEDCB", move_four(&CrateMover9000));
+        assert_eq!("XBCDE", move_four(&CrateMover9001));
+        assert_eq!("XEDCB", move_four(&LimitedCrane::new(1)));
+        assert_eq!("XDEBC", move_four(&LimitedCrane::new(2)));
+        assert_eq!("XCDEB", move_four(&LimitedCrane::new(3)));
+        assert_eq!("XBCDE", move_four(&LimitedCrane::new(4)));
+    }
+
+    #[test]
+    fn select_crane_by_name() {
+        let model = |name: &str| name.parse::<CraneModel>().map(|crane| move_four(&crane));
+
+        assert_eq!(Ok("XEDCB"), model("9000").as_deref());
+        assert_eq!(Ok("XBCDE"), model("9001").as_deref());
+        assert_eq!(Ok("XCDEB"), model("limited:3").as_deref());
+        assert!(model("limited:0").is_err());
+        assert_eq!(
+            "Unknown crane '9002'. Expected '9000', '9001' or 'limited:<capacity>'.",
+            model("9002").unwrap_err().to_string()
+        );
+    }
+}
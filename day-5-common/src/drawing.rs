@@ -0,0 +1,266 @@
+//! Parses and renders the drawing of the stacks, e.g.:
+//!
+//! ```text
+//!     [D]    
+//! [N] [C]    
+//! [Z] [M] [P]
+//!  1   2   3
+//! ```
+//!
+//! The numbers in the last line (the footer) locate the stacks. A crate belongs to the stack whose
+//! number is written below it. This way there may be more than nine stacks, and labels may be
+//! longer than a single character, e.g. `[AB]`.
+
+use std::{error::Error, fmt};
+
+use atoi::FromRadix10Checked;
+
+use crate::Crates;
+
+/// Reason why the drawing of the stacks could not be parsed. Lines and columns refer to the input,
+/// starting at `1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidDrawing {
+    /// There is no line numbering the stacks.
+    MissingFooter,
+    /// Footer contains something else than the numbers `1`, `2`, `3`, ... in ascending order.
+    InvalidFooter { column: usize },
+    /// Crate is missing its closing bracket.
+    UnclosedCrate { line: usize, column: usize },
+    /// There is no stack number below the crate.
+    CrateOutsideStacks { line: usize, column: usize },
+}
+
+impl fmt::Display for InvalidDrawing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidDrawing::MissingFooter => write!(f, "Drawing does not number the stacks"),
+            InvalidDrawing::InvalidFooter { column } => write!(
+                f,
+                "Expected stacks to be numbered 1, 2, 3, ... in the last line of the drawing. \
+                Found something else in column {column}."
+            ),
+            InvalidDrawing::UnclosedCrate { line, column } => {
+                write!(
+                    f,
+                    "Crate in line {line}, column {column} is missing its ']'"
+                )
+            }
+            InvalidDrawing::CrateOutsideStacks { line, column } => write!(
+                f,
+                "Crate in line {line}, column {column} is not above any stack number"
+            ),
+        }
+    }
+}
+
+impl Error for InvalidDrawing {}
+
+impl Crates {
+    /// Parses the drawing of the stacks, including the footer numbering them. Lines are separated
+    /// by `\n`, trailing whitespace is ignored.
+    pub fn parse(drawing: &[u8]) -> Result<Self, InvalidDrawing> {
+        let mut lines: Vec<&[u8]> = drawing
+            .split(|&byte| byte == b'\n')
+            .map(|line| line.trim_ascii_end())
+            .collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let footer = lines.pop().ok_or(InvalidDrawing::MissingFooter)?;
+        let columns = columns(footer)?;
+
+        let mut stacks = vec![Vec::new(); columns.len()];
+        // Crates are drawn top down, so we fill the stacks starting with the lowest line
+        for (index, line) in lines.iter().enumerate().rev() {
+            let line_number = index + 1;
+            let mut pos = 0;
+            while let Some(offset) = line[pos..].iter().position(|&byte| byte == b'[') {
+                let open = pos + offset;
+                let position = |index: usize| (line_number, index + 1);
+                let close = line[open..]
+                    .iter()
+                    .position(|&byte| byte == b']')
+                    .map(|offset| open + offset)
+                    .ok_or_else(|| {
+                        let (line, column) = position(open);
+                        InvalidDrawing::UnclosedCrate { line, column }
+                    })?;
+                let stack = columns
+                    .iter()
+                    .position(|&(start, end)| start <= close && open < end)
+                    .ok_or_else(|| {
+                        let (line, column) = position(open);
+                        InvalidDrawing::CrateOutsideStacks { line, column }
+                    })?;
+                let label = String::from_utf8_lossy(&line[open + 1..close]).into_owned();
+                stacks[stack].push(label);
+                pos = close + 1;
+            }
+        }
+
+        Ok(Self { stacks })
+    }
+}
+
+/// Byte range `(start, end)` of each stack number in the footer.
+fn columns(footer: &[u8]) -> Result<Vec<(usize, usize)>, InvalidDrawing> {
+    let mut columns = Vec::new();
+    let mut pos = 0;
+    while pos < footer.len() {
+        if footer[pos] == b' ' {
+            pos += 1;
+            continue;
+        }
+        let (number, num_digits) = usize::from_radix_10_checked(&footer[pos..]);
+        let end = pos + num_digits;
+        let is_separated = footer.get(end).is_none_or(|&byte| byte == b' ');
+        if number != Some(columns.len() + 1) || !is_separated {
+            return Err(InvalidDrawing::InvalidFooter { column: pos + 1 });
+        }
+        columns.push((pos, end));
+        pos = end;
+    }
+    if columns.is_empty() {
+        return Err(InvalidDrawing::MissingFooter);
+    }
+    Ok(columns)
+}
+
+/// Draws each crate as `[X]`, with the stacks side by side and the stack numbers below. Columns
+/// are as wide as their longest label (or stack number) requires. With single character labels and
+/// up to nine stacks, this is exactly the drawing of the puzzle.
+impl fmt::Display for Crates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths: Vec<usize> = self
+            .stacks
+            .iter()
+            .enumerate()
+            .map(|(index, stack)| {
+                let label_width = stack.iter().map(|label| label.len() + 2).max().unwrap_or(3);
+                label_width.max((index + 1).to_string().len())
+            })
+            .collect();
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            for (index, (stack, &width)) in self.stacks.iter().zip(&widths).enumerate() {
+                if index != 0 {
+                    write!(f, " ")?;
+                }
+                match stack.get(level) {
+                    Some(label) => write!(f, "{:<width$}", format!("[{label}]"))?,
+                    None => write!(f, "{:width$}", "")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        let mut footer = String::new();
+        for (index, &width) in widths.iter().enumerate() {
+            if index != 0 {
+                footer.push(' ');
+            }
+            let number = (index + 1).to_string();
+            let padding = (width - number.len()) / 2;
+            footer += &format!("{:padding$}{number:<rest$}", "", rest = width - padding);
+        }
+        writeln!(f, "{}", footer.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CrateMover9000, Crates, Instruction};
+
+    use super::InvalidDrawing;
+
+    const DRAWING: &str = "    [D]    \n\
+                           [N] [C]    \n\
+                           [Z] [M] [P]\n \
+                            1   2   3\n";
+
+    #[test]
+    fn draw_stacks() {
+        let crates = Crates::parse(DRAWING.as_bytes()).unwrap();
+
+        assert_eq!(DRAWING, crates.to_string());
+    }
+
+    #[test]
+    fn draw_after_move() {
+        let mut crates = Crates::parse(DRAWING.as_bytes()).unwrap();
+
+        crates
+            .apply(
+                &Instruction::from_line(b"move 3 from 2 to 3"),
+                &CrateMover9000,
+            )
+            .unwrap();
+
+        let expected = "        [M]\n\
+                        \x20       [C]\n\
+                        [N]     [D]\n\
+                        [Z]     [P]\n \
+                         1   2   3\n";
+        assert_eq!(expected, crates.to_string());
+    }
+
+    #[test]
+    fn more_than_nine_stacks() {
+        let drawing = "\
+            [A]                                     [K]\n\
+            [B] [C] [D] [E] [F] [G] [H] [I] [J] [L] [M]\n \
+             1   2   3   4   5   6   7   8   9  10  11\n";
+
+        let crates = Crates::parse(drawing.as_bytes()).unwrap();
+
+        assert_eq!("ACDEFGHIJLK", crates.top_crates());
+        assert_eq!(drawing, crates.to_string());
+    }
+
+    #[test]
+    fn multi_character_labels() {
+        let drawing = "\
+            [AB]\n\
+            [C]   [DEF]\n \
+             1      2\n";
+
+        let crates = Crates::parse(drawing.as_bytes()).unwrap();
+
+        assert_eq!("ABDEF", crates.top_crates());
+        let expected = "\
+            [AB]\n\
+            [C]  [DEF]\n \
+             1     2\n";
+        assert_eq!(
+            expected,
+            crates
+                .to_string()
+                .lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n")
+                + "\n"
+        );
+    }
+
+    #[test]
+    fn invalid_drawings() {
+        let parse = |drawing: &str| Crates::parse(drawing.as_bytes()).unwrap_err();
+
+        assert_eq!(InvalidDrawing::MissingFooter, parse(""));
+        assert_eq!(InvalidDrawing::MissingFooter, parse("   \n\n"));
+        assert_eq!(
+            InvalidDrawing::InvalidFooter { column: 6 },
+            parse(" 1   3\n")
+        );
+        assert_eq!(InvalidDrawing::InvalidFooter { column: 2 }, parse(" 1x\n"));
+        assert_eq!(
+            InvalidDrawing::UnclosedCrate { line: 1, column: 5 },
+            parse("[A] [B\n 1   2\n")
+        );
+        assert_eq!(
+            InvalidDrawing::CrateOutsideStacks { line: 2, column: 5 },
+            parse("[A]\n[B] [C]\n 1\n")
+        );
+    }
+}
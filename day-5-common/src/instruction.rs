@@ -0,0 +1,187 @@
+//! Parses and validates the rearrangement procedure below the drawing.
+
+use std::{error::Error, fmt, io};
+
+use atoi::FromRadix10Checked;
+
+use crate::InvalidDrawing;
+
+/// Single step of the rearrangement procedure, e.g. `move 1 from 2 to 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    /// Number of crates to move
+    pub amount: usize,
+    /// Number of the stack to take the crates from, starting at `1`
+    pub from: usize,
+    /// Number of the stack to put the crates onto, starting at `1`
+    pub to: usize,
+}
+
+impl Instruction {
+    /// # Panics
+    ///
+    /// If the line is not a valid instruction. See [`Self::parse`].
+    pub fn from_line(line: &[u8]) -> Instruction {
+        Self::parse(line).expect("Invalid instruction")
+    }
+
+    /// Parses `move <amount> from <stack> to <stack>`. Words may be separated by any amount of
+    /// whitespace. Whether the stacks exist is checked when the instruction is applied.
+    pub fn parse(line: &[u8]) -> Result<Instruction, InvalidInstruction> {
+        let mut words = line
+            .split(|byte| byte.is_ascii_whitespace())
+            .filter(|word| !word.is_empty());
+        let mut number_after = |keyword: &[u8]| {
+            if words.next() != Some(keyword) {
+                return None;
+            }
+            let word = words.next()?;
+            match usize::from_radix_10_checked(word) {
+                (Some(number), num_digits) if num_digits == word.len() => Some(number),
+                _ => None,
+            }
+        };
+        let amount = number_after(b"move");
+        let from = number_after(b"from");
+        let to = number_after(b"to");
+        match (amount, from, to, words.next()) {
+            (Some(amount), Some(from), Some(to), None) => Ok(Instruction { amount, from, to }),
+            _ => Err(InvalidInstruction::Malformed),
+        }
+    }
+}
+
+/// Same format as in the puzzle input, e.g. `move 1 from 2 to 1`.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.amount, self.from, self.to)
+    }
+}
+
+/// Reason why an instruction can not be carried out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidInstruction {
+    /// Line is not of the form `move <amount> from <stack> to <stack>`.
+    Malformed,
+    /// Instruction does not move any crates.
+    ZeroMove,
+    /// Stack number is zero or exceeds the number of stacks.
+    StackOutOfRange { stack: usize, num_stacks: usize },
+    /// Stack runs empty before all crates have been moved.
+    EmptyStack {
+        stack: usize,
+        amount: usize,
+        available: usize,
+    },
+}
+
+impl fmt::Display for InvalidInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidInstruction::Malformed => {
+                write!(f, "expected 'move <amount> from <stack> to <stack>'")
+            }
+            InvalidInstruction::ZeroMove => write!(f, "must move at least one crate"),
+            InvalidInstruction::StackOutOfRange { stack, num_stacks } => write!(
+                f,
+                "there is no stack {stack}, stacks are numbered from 1 to {num_stacks}"
+            ),
+            InvalidInstruction::EmptyStack {
+                stack,
+                amount,
+                available,
+            } => write!(
+                f,
+                "can not move {amount} crates from stack {stack}, which only holds {available}"
+            ),
+        }
+    }
+}
+
+impl Error for InvalidInstruction {}
+
+/// Reason why the rearrangement procedure could not be completed.
+#[derive(Debug)]
+pub enum RearrangeError {
+    InvalidDrawing(InvalidDrawing),
+    /// Instruction in the line (starting at `1`) is invalid.
+    InvalidInstruction {
+        line: usize,
+        reason: InvalidInstruction,
+    },
+    /// Writing the trace failed.
+    Trace(io::Error),
+}
+
+impl fmt::Display for RearrangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RearrangeError::InvalidDrawing(reason) => reason.fmt(f),
+            RearrangeError::InvalidInstruction { line, reason } => {
+                write!(f, "Invalid instruction in line {line}: {reason}")
+            }
+            RearrangeError::Trace(error) => write!(f, "Can not write trace: {error}"),
+        }
+    }
+}
+
+impl Error for RearrangeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RearrangeError::InvalidDrawing(reason) => Some(reason),
+            RearrangeError::InvalidInstruction { reason, .. } => Some(reason),
+            RearrangeError::Trace(error) => Some(error),
+        }
+    }
+}
+
+impl From<InvalidDrawing> for RearrangeError {
+    fn from(reason: InvalidDrawing) -> Self {
+        RearrangeError::InvalidDrawing(reason)
+    }
+}
+
+impl From<io::Error> for RearrangeError {
+    fn from(error: io::Error) -> Self {
+        RearrangeError::Trace(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Instruction, InvalidInstruction};
+
+    #[test]
+    fn parse_instructions() {
+        let instruction = |amount, from, to| Ok(Instruction { amount, from, to });
+
+        assert_eq!(
+            instruction(1, 2, 1),
+            Instruction::parse(b"move 1 from 2 to 1")
+        );
+        assert_eq!(
+            instruction(12, 10, 3),
+            Instruction::parse(b"  move 12  from\t10 to 3\r")
+        );
+        assert_eq!(
+            "move 12 from 10 to 3",
+            Instruction::from_line(b"move 12 from 10 to 3").to_string()
+        );
+    }
+
+    #[test]
+    fn reject_malformed_instructions() {
+        for line in [
+            &b""[..],
+            b"move 1 from 2",
+            b"move 1 from 2 to",
+            b"move one from 2 to 1",
+            b"move 1 from 2 to 1 now",
+            b"move 1 to 2 from 1",
+            b"move -1 from 2 to 1",
+            b"move 1x from 2 to 1",
+        ] {
+            assert_eq!(Err(InvalidInstruction::Malformed), Instruction::parse(line));
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Crate stacks of Day 5: Supply Stacks. Both parts rearrange the same stacks following the same
+//! instructions, but with different cranes. See [`Crane`].
+
+use std::io::{BufRead, Write};
+
+use common::LineStream;
+
+pub use self::{
+    cli::{run, RunError},
+    crane::{Crane, CraneModel, CrateMover9000, CrateMover9001, LimitedCrane, UnknownCrane},
+    drawing::InvalidDrawing,
+    instruction::{Instruction, InvalidInstruction, RearrangeError},
+};
+
+mod cli;
+mod crane;
+mod drawing;
+mod instruction;
+
+/// Parses the drawing of the stacks and rearranges them following the instructions below it.
+///
+/// If `trace` is given, the stacks are drawn into it initially and after each instruction.
+pub fn rearrange(
+    input: impl BufRead,
+    crane: &(impl Crane + ?Sized),
+    mut trace: Option<&mut dyn Write>,
+) -> Result<Crates, RearrangeError> {
+    let mut lines = LineStream::new(input);
+    // Drawing ends with the first empty line
+    let mut drawing = Vec::new();
+    let mut line_number = 0;
+    while let Some(line) = lines.next_line() {
+        line_number += 1;
+        if line.trim_ascii_end().is_empty() {
+            break;
+        }
+        drawing.extend_from_slice(line);
+        drawing.push(b'\n');
+    }
+    let mut crates = Crates::parse(&drawing)?;
+    if let Some(out) = trace.as_mut() {
+        writeln!(out, "{crates}")?;
+    }
+
+    while let Some(line) = lines.next_line() {
+        line_number += 1;
+        let invalid = |reason| RearrangeError::InvalidInstruction {
+            line: line_number,
+            reason,
+        };
+        let instruction = Instruction::parse(line).map_err(invalid)?;
+        crates.apply(&instruction, crane).map_err(invalid)?;
+        if let Some(out) = trace.as_mut() {
+            writeln!(out, "{instruction}\n{crates}")?;
+        }
+    }
+
+    Ok(crates)
+}
+
+#[derive(Debug, Clone)]
+pub struct Crates {
+    // Stacks with crates in bottom up order (the lowest crate is first).
+    stacks: Vec<Vec<String>>,
+}
+
+impl Crates {
+    /// Moves crates with the crane, unless the instruction refers to stacks which do not exist or
+    /// moves more crates than the stack holds.
+    pub fn apply(
+        &mut self,
+        instruction: &Instruction,
+        crane: &(impl Crane + ?Sized),
+    ) -> Result<(), InvalidInstruction> {
+        let num_stacks = self.stacks.len();
+        if instruction.amount == 0 {
+            return Err(InvalidInstruction::ZeroMove);
+        }
+        for stack in [instruction.from, instruction.to] {
+            if !(1..=num_stacks).contains(&stack) {
+                return Err(InvalidInstruction::StackOutOfRange { stack, num_stacks });
+            }
+        }
+        let (from, to) = (instruction.from - 1, instruction.to - 1);
+        let available = self.stacks[from].len();
+        if available < instruction.amount {
+            return Err(InvalidInstruction::EmptyStack {
+                stack: instruction.from,
+                amount: instruction.amount,
+                available,
+            });
+        }
+        // Moving crates onto the stack they came from does not change anything, no matter the
+        // crane.
+        if from == to {
+            return Ok(());
+        }
+        let (from, to) = if from < to {
+            let (lower, upper) = self.stacks.split_at_mut(to);
+            (&mut lower[from], &mut upper[0])
+        } else {
+            let (lower, upper) = self.stacks.split_at_mut(from);
+            (&mut upper[0], &mut lower[to])
+        };
+        crane.move_crates(instruction.amount, from, to);
+        Ok(())
+    }
+
+    /// Labels of the crates on top of each stack. Empty stacks are skipped.
+    pub fn top_crates(&self) -> String {
+        self.stacks
+            .iter()
+            .filter_map(|stack| stack.last().map(String::as_str))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        rearrange, Crane, CrateMover9000, CrateMover9001, Crates, Instruction, InvalidInstruction,
+        LimitedCrane, RearrangeError,
+    };
+
+    const INPUT: &str = "    \
+            [D]           \n\
+        [N] [C]           \n\
+        [Z] [M] [P]       \n \
+         1   2   3        \n\
+        \n\
+        move 1 from 2 to 1\n\
+        move 3 from 1 to 3\n\
+        move 2 from 2 to 1\n\
+        move 1 from 1 to 2\n\
+    ";
+
+    fn top_crates(crane: &dyn Crane) -> String {
+        rearrange(INPUT.as_bytes(), crane, None)
+            .unwrap()
+            .top_crates()
+    }
+
+    #[test]
+    fn example_given() {
+        assert_eq!("CMZ", top_crates(&CrateMover9000));
+        assert_eq!("MCD", top_crates(&CrateMover9001));
+        assert_eq!("CMZ", top_crates(&LimitedCrane::new(1)));
+        // Only the move of three crates is split. The lowest one is lifted last and ends up on top.
+        assert_eq!("MCZ", top_crates(&LimitedCrane::new(2)));
+    }
+
+    #[test]
+    fn move_to_lower_stack() {
+        let mut crates = Crates::parse(b"[A] [B]\n 1   2 \n").unwrap();
+        let instruction = Instruction {
+            amount: 1,
+            from: 2,
+            to: 1,
+        };
+
+        crates.apply(&instruction, &CrateMover9000).unwrap();
+
+        assert_eq!("B", crates.top_crates());
+    }
+
+    #[test]
+    fn trace_each_step() {
+        let input = "[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\n";
+        let mut trace = Vec::new();
+
+        rearrange(input.as_bytes(), &CrateMover9000, Some(&mut trace)).unwrap();
+
+        let expected = "\
+            [A] [B]\n \
+             1   2\n\
+            \n\
+            move 1 from 1 to 2\n\
+            \x20   [A]\n\
+            \x20   [B]\n \
+             1   2\n\
+            \n\
+        ";
+        assert_eq!(expected, String::from_utf8(trace).unwrap());
+    }
+
+    #[test]
+    fn report_invalid_instructions() {
+        let error = |instruction: &str| {
+            let input = format!("[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\n{instruction}\n");
+            match rearrange(input.as_bytes(), &CrateMover9000, None) {
+                Err(RearrangeError::InvalidInstruction { line, reason }) => (line, reason),
+                _ => panic!("Expected invalid instruction"),
+            }
+        };
+
+        assert_eq!((5, InvalidInstruction::Malformed), error("move 1 from 1"));
+        assert_eq!(
+            (5, InvalidInstruction::ZeroMove),
+            error("move 0 from 2 to 1")
+        );
+        assert_eq!(
+            (
+                5,
+                InvalidInstruction::StackOutOfRange {
+                    stack: 3,
+                    num_stacks: 2
+                }
+            ),
+            error("move 1 from 2 to 3")
+        );
+        assert_eq!(
+            (
+                5,
+                InvalidInstruction::StackOutOfRange {
+                    stack: 0,
+                    num_stacks: 2
+                }
+            ),
+            error("move 1 from 0 to 1")
+        );
+        assert_eq!(
+            (
+                5,
+                InvalidInstruction::EmptyStack {
+                    stack: 1,
+                    amount: 1,
+                    available: 0
+                }
+            ),
+            error("move 1 from 1 to 2")
+        );
+        assert_eq!(
+            "Invalid instruction in line 5: can not move 3 crates from stack 2, which only holds 2",
+            rearrange(
+                "[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\nmove 3 from 2 to 2\n".as_bytes(),
+                &CrateMover9000,
+                None
+            )
+            .unwrap_err()
+            .to_string()
+        );
+    }
+}
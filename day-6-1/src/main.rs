@@ -44,6 +44,7 @@
 //!
 //! How many characters need to be processed before the first start-of-packet marker is detected?
 
+use day_6_common::find_marker;
 use std::{
     fs::File,
     io::{BufRead, BufReader},
@@ -55,22 +56,9 @@ fn main() {
     println!("{start_index}");
 }
 
+/// Number of characters processed before the first start-of-packet marker is complete.
 fn start_of_packet(input: impl BufRead) -> Option<usize> {
-    let mut bytes = input
-        .bytes()
-        .map(|res| res.expect("IO must work"))
-        .enumerate();
-    let (_, first) = bytes.next()?;
-    bytes
-        .scan([first; 4], |last, (pos, byte)| {
-            last[pos % last.len()] = byte;
-            Some(*last)
-        })
-        .position(|last| {
-            (0..last.len())
-                .all(|index| ((index + 1)..last.len()).all(|other| last[index] != last[other]))
-        })
-        .map(|pos| pos + 2)
+    find_marker(input, 4).next()
 }
 
 #[cfg(test)]
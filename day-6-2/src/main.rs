@@ -61,6 +61,7 @@
 //!
 //! How many characters need to be processed before the first start-of-message marker is detected?
 
+use day_6_common::find_marker;
 use std::{
     fs::File,
     io::{BufRead, BufReader},
@@ -68,46 +69,33 @@ use std::{
 
 fn main() {
     let input = BufReader::new(File::open("input.txt").expect("Can not open input file"));
-    let start_index = start_of_packet(input).expect("No start packet in input");
+    let start_index = start_of_message(input).expect("No start of message in input");
     println!("{start_index}");
 }
 
-fn start_of_packet(input: impl BufRead) -> Option<usize> {
-    let mut bytes = input
-        .bytes()
-        .map(|res| res.expect("IO must work"))
-        .enumerate();
-    let (_, first) = bytes.next()?;
-    bytes
-        .scan([first; 14], |last, (pos, byte)| {
-            last[pos % last.len()] = byte;
-            Some(*last)
-        })
-        .position(|last| {
-            (0..last.len())
-                .all(|index| ((index + 1)..last.len()).all(|other| last[index] != last[other]))
-        })
-        .map(|pos| pos + 2)
+/// Number of characters processed before the first start-of-message marker is complete.
+fn start_of_message(input: impl BufRead) -> Option<usize> {
+    find_marker(input, 14).next()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::start_of_packet;
+    use crate::start_of_message;
 
     #[test]
     fn input_too_short() {
         let input = "123".as_bytes();
-        let pos = start_of_packet(input);
+        let pos = start_of_message(input);
         assert!(pos.is_none());
     }
 
     #[test]
-    fn find_start_of_packet() {
-        let sop = |input: &str| start_of_packet(input.as_bytes()).unwrap();
-        assert_eq!(19, sop("mjqjpqmgbljsphdztnvjfqwrcgsmlb"));
-        assert_eq!(23, sop("bvwbjplbgvbhsrlpgdmjqwftvncz"));
-        assert_eq!(23, sop("nppdvjthqldpwncqszvftbrmjlhg"));
-        assert_eq!(29, sop("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"));
-        assert_eq!(26, sop("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"));
+    fn find_start_of_message() {
+        let som = |input: &str| start_of_message(input.as_bytes()).unwrap();
+        assert_eq!(19, som("mjqjpqmgbljsphdztnvjfqwrcgsmlb"));
+        assert_eq!(23, som("bvwbjplbgvbhsrlpgdmjqwftvncz"));
+        assert_eq!(23, som("nppdvjthqldpwncqszvftbrmjlhg"));
+        assert_eq!(29, som("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"));
+        assert_eq!(26, som("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"));
     }
 }
@@ -0,0 +1,60 @@
+//! Compares the ways of finding the start-of-message marker in a long stream, which contains no
+//! marker until its very end.
+
+use std::io::{BufRead, BufReader};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day_6_common::{find_marker, BitmaskMarkers};
+
+const WINDOW: usize = 14;
+
+/// Repeats thirteen distinct letters, so no window of fourteen is a marker, followed by the whole
+/// alphabet.
+fn stream(len: usize) -> Vec<u8> {
+    let mut bytes: Vec<u8> = b"abcdefghijklm".iter().copied().cycle().take(len).collect();
+    bytes.extend(b'a'..=b'z');
+    bytes
+}
+
+/// The original solution: a ring buffer of the last bytes, which compares all pairs at every
+/// position.
+fn scan_position(input: impl BufRead) -> Option<usize> {
+    let mut bytes = input
+        .bytes()
+        .map(|res| res.expect("IO must work"))
+        .enumerate();
+    let (_, first) = bytes.next()?;
+    bytes
+        .scan([first; WINDOW], |last, (pos, byte)| {
+            last[pos % last.len()] = byte;
+            Some(*last)
+        })
+        .position(|last| {
+            (0..last.len())
+                .all(|index| ((index + 1)..last.len()).all(|other| last[index] != last[other]))
+        })
+        .map(|pos| pos + 2)
+}
+
+fn markers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("markers");
+    for len in [100_000, 10_000_000] {
+        let input = stream(len);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("scan + position", len),
+            &input,
+            |b, input| b.iter(|| scan_position(BufReader::new(&input[..]))),
+        );
+        group.bench_with_input(BenchmarkId::new("count table", len), &input, |b, input| {
+            b.iter(|| find_marker(BufReader::new(&input[..]), WINDOW).next())
+        });
+        group.bench_with_input(BenchmarkId::new("bitmask", len), &input, |b, input| {
+            b.iter(|| BitmaskMarkers::new(&input[..], WINDOW).next())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, markers);
+criterion_main!(benches);
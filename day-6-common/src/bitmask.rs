@@ -0,0 +1,158 @@
+//! Fast path for long streams of lowercase letters. Reads the input in large chunks and represents
+//! the window as a bitmask with one bit per letter. Both adding a byte to the window and removing
+//! one toggle its bit. Duplicates cancel each other out, so the window is a marker exactly if the
+//! number of set bits equals the window size.
+
+use std::io::{self, ErrorKind, Read};
+
+/// Number of bytes read from the input at once
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Bit of each byte within the mask. Only lowercase letters have one, all other bytes map to `0`
+/// and therefore are never part of a marker.
+const BITS: [u32; 256] = {
+    let mut bits = [0; 256];
+    let mut letter = b'a';
+    while letter <= b'z' {
+        bits[letter as usize] = 1 << (letter - b'a');
+        letter += 1;
+    }
+    bits
+};
+
+/// Like [`crate::Markers`], but markers may only consist of lowercase letters.
+pub struct BitmaskMarkers<R> {
+    input: R,
+    window: usize,
+    /// The `window` bytes preceding `start`, followed by the bytes yet to process. Initially the
+    /// bytes preceding the stream are zeros, which have no bit and therefore do not need any
+    /// special treatment while the window fills up.
+    buf: Vec<u8>,
+    /// Index of the next byte to process within `buf`
+    start: usize,
+    /// Number of valid bytes in `buf`
+    len: usize,
+    /// Bytes of the stream shifted out of the front of `buf` so far
+    dropped: usize,
+    /// XOR of the bits of all bytes within the window
+    mask: u32,
+}
+
+impl<R> BitmaskMarkers<R> {
+    /// # Panics
+    ///
+    /// If `window` is zero
+    pub fn new(input: R, window: usize) -> Self {
+        assert!(window != 0, "Marker must consist of at least one byte");
+        Self {
+            input,
+            window,
+            buf: vec![0; window + CHUNK_SIZE],
+            start: window,
+            len: window,
+            dropped: 0,
+            mask: 0,
+        }
+    }
+}
+
+impl<R> BitmaskMarkers<R>
+where
+    R: Read,
+{
+    /// Keeps the last `window` bytes and fills up the rest of the buffer. `false` at the end of
+    /// the stream.
+    fn refill(&mut self) -> io::Result<bool> {
+        self.buf.copy_within(self.len - self.window..self.len, 0);
+        self.dropped += self.len - self.window;
+        self.start = self.window;
+        self.len = self.window;
+        loop {
+            match self.input.read(&mut self.buf[self.window..]) {
+                Ok(0) => return Ok(false),
+                Ok(num_bytes) => {
+                    self.len += num_bytes;
+                    return Ok(true);
+                }
+                Err(error) if error.kind() == ErrorKind::Interrupted => (),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+impl<R> Iterator for BitmaskMarkers<R>
+where
+    R: Read,
+{
+    type Item = io::Result<usize>;
+
+    fn next(&mut self) -> Option<io::Result<usize>> {
+        let window = self.window;
+        loop {
+            let chunk = &self.buf[..self.len];
+            for index in self.start..self.len {
+                self.mask ^= BITS[chunk[index] as usize] ^ BITS[chunk[index - window] as usize];
+                if self.mask.count_ones() as usize == window {
+                    self.start = index + 1;
+                    return Some(Ok(self.dropped + self.start - window));
+                }
+            }
+            self.start = self.len;
+            match self.refill() {
+                Ok(true) => (),
+                Ok(false) => return None,
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BitmaskMarkers, CHUNK_SIZE};
+    use crate::find_marker;
+
+    fn first(input: &str, window: usize) -> Option<usize> {
+        BitmaskMarkers::new(input.as_bytes(), window)
+            .next()
+            .map(Result::unwrap)
+    }
+
+    #[test]
+    fn start_of_packet_and_message() {
+        assert_eq!(Some(7), first("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4));
+        assert_eq!(Some(11), first("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4));
+        assert_eq!(Some(19), first("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14));
+        assert_eq!(Some(26), first("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14));
+        assert_eq!(None, first("abc", 4));
+    }
+
+    #[test]
+    fn only_letters_form_markers() {
+        assert_eq!(None, first("1234", 4));
+        assert_eq!(Some(6), first("ab\ncde", 3));
+    }
+
+    #[test]
+    fn markers_across_chunk_boundaries() {
+        // Long stretches without a marker, ending in markers which straddle the chunks
+        let input: Vec<u8> = b"abcabc"
+            .iter()
+            .cycle()
+            .take(CHUNK_SIZE - 2)
+            .chain(b"defghijklmn".iter())
+            .chain(b"abcabc".iter().cycle().take(2 * CHUNK_SIZE + 7))
+            .chain(b"xyzw".iter())
+            .copied()
+            .collect();
+
+        let expected: Vec<_> = find_marker(&input[..], 4).collect();
+        let actual: Vec<_> = BitmaskMarkers::new(&input[..], 4)
+            .map(Result::unwrap)
+            .collect();
+
+        assert!(!expected.is_empty());
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,137 @@
+//! Marker detection for Day 6: Tuning Trouble. A marker is a sequence of distinct characters. Part
+//! one looks for the start-of-packet marker with four characters, part two for the
+//! start-of-message marker with fourteen.
+//!
+//! Rather than comparing all pairs of characters within the window at every position, we keep
+//! count of how often each byte occurs within the window and of how many bytes occur more than
+//! once. Moving the window by one byte then only updates two counts. For long streams of lowercase
+//! letters [`BitmaskMarkers`] is faster still.
+
+use std::io::BufRead;
+
+pub use self::bitmask::BitmaskMarkers;
+
+mod bitmask;
+
+/// Positions right after each marker of `window` distinct bytes, i.e. the number of bytes
+/// processed once the marker is complete. Overlapping markers are all reported.
+///
+/// # Panics
+///
+/// If `window` is zero, or if reading the input fails.
+pub fn find_marker(input: impl BufRead, window: usize) -> Markers<impl Iterator<Item = u8>> {
+    let bytes = input.bytes().map(|res| res.expect("IO must work"));
+    Markers::new(bytes, window)
+}
+
+/// Iterator over the marker positions within a stream of bytes. See [`find_marker`].
+pub struct Markers<I> {
+    bytes: I,
+    /// Last `window` bytes. The byte at `pos % window` is the next to leave the window.
+    ring: Vec<u8>,
+    /// Number of bytes processed so far
+    pos: usize,
+    /// How often each byte occurs within the window
+    counts: [u32; 256],
+    /// Number of distinct bytes occurring more than once within the window
+    num_duplicates: usize,
+}
+
+impl<I> Markers<I> {
+    /// # Panics
+    ///
+    /// If `window` is zero
+    pub fn new(bytes: I, window: usize) -> Self {
+        assert!(window != 0, "Marker must consist of at least one byte");
+        Self {
+            bytes,
+            ring: vec![0; window],
+            pos: 0,
+            counts: [0; 256],
+            num_duplicates: 0,
+        }
+    }
+
+    /// Moves the window by one byte.
+    fn push(&mut self, byte: u8) {
+        let window = self.ring.len();
+        let slot = self.pos % window;
+        if self.pos >= window {
+            let leaving = self.ring[slot] as usize;
+            if self.counts[leaving] == 2 {
+                self.num_duplicates -= 1;
+            }
+            self.counts[leaving] -= 1;
+        }
+        self.ring[slot] = byte;
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 2 {
+            self.num_duplicates += 1;
+        }
+        self.pos += 1;
+    }
+}
+
+impl<I> Iterator for Markers<I>
+where
+    I: Iterator<Item = u8>,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while let Some(byte) = self.bytes.next() {
+            self.push(byte);
+            if self.pos >= self.ring.len() && self.num_duplicates == 0 {
+                return Some(self.pos);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::find_marker;
+
+    fn first(input: &str, window: usize) -> Option<usize> {
+        find_marker(input.as_bytes(), window).next()
+    }
+
+    #[test]
+    fn start_of_packet() {
+        assert_eq!(Some(7), first("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4));
+        assert_eq!(Some(5), first("bvwbjplbgvbhsrlpgdmjqwftvncz", 4));
+        assert_eq!(Some(6), first("nppdvjthqldpwncqszvftbrmjlhg", 4));
+        assert_eq!(Some(10), first("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4));
+        assert_eq!(Some(11), first("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4));
+    }
+
+    #[test]
+    fn start_of_message() {
+        assert_eq!(Some(19), first("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14));
+        assert_eq!(Some(23), first("bvwbjplbgvbhsrlpgdmjqwftvncz", 14));
+        assert_eq!(Some(23), first("nppdvjthqldpwncqszvftbrmjlhg", 14));
+        assert_eq!(Some(29), first("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14));
+        assert_eq!(Some(26), first("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14));
+    }
+
+    #[test]
+    fn input_too_short() {
+        assert_eq!(None, first("123", 4));
+        assert_eq!(Some(4), first("1234", 4));
+    }
+
+    #[test]
+    fn all_markers() {
+        let markers: Vec<_> = find_marker("aabcabbc".as_bytes(), 3).collect();
+
+        assert_eq!(vec![4, 5, 6], markers);
+    }
+
+    #[test]
+    fn window_of_one() {
+        let markers: Vec<_> = find_marker("aab".as_bytes(), 1).collect();
+
+        assert_eq!(vec![1, 2, 3], markers);
+    }
+}
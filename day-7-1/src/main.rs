@@ -102,176 +102,36 @@
 //!
 //! **What is the sum of the total sizes of those directories?**
 
-use atoi::FromRadix10Checked;
-use lines::LineStream;
+use anyhow::Error;
+use day_7_common::{Accumulator, FileSystem, InvalidTranscript, SizeSum};
 use std::{
     fs::File,
     io::{BufRead, BufReader},
 };
 
-mod lines;
-
-fn main() {
-    let input = BufReader::new(File::open("input.txt").expect("Can not open input file"));
-    let size = accumulated_directory_size(input);
+fn main() -> Result<(), Error> {
+    let input = BufReader::new(File::open("input.txt")?);
+    let size = accumulated_directory_size(input)?;
     println!("{size}");
+    Ok(())
 }
 
-fn accumulated_directory_size(input: impl BufRead) -> u64 {
-    let lines = LineStream::new(input);
-    let mut terminal_output = TerminalOutput::new(lines);
-    let first_command = terminal_output.next();
-    assert_eq!(Some(Log::ToRoot), first_command);
-    rec_directory_size(&mut terminal_output).accumulated_size
-}
-
-struct AggregatedSize {
-    child_size: u64,
-    accumulated_size: u64,
-}
-
-// The text does not specify this, but the input is a straight forward depth first search, so we
-// won't keep track of any directory names
-fn rec_directory_size(to: &mut TerminalOutput<impl BufRead>) -> AggregatedSize {
-    let list = to.next();
-    assert_eq!(Some(Log::Ls), list);
-    let mut directory_size = 0;
-    let mut accumulated_size = 0;
-    while let Some(log) = to.next() {
-        match log {
-            Log::ToRoot | Log::Ls => panic!("yagni"),
-            Log::ToChild => {
-                let agg_size = rec_directory_size(to);
-                directory_size += agg_size.child_size;
-                accumulated_size += agg_size.accumulated_size;
-            }
-            Log::ToParent => break,
-            Log::Directory => (),
-            Log::File(size) => directory_size += size,
-        }
-    }
-    if directory_size <= 100_000 {
-        accumulated_size += directory_size;
-    }
-    AggregatedSize {
-        child_size: directory_size,
-        accumulated_size,
-    }
-}
-
-struct TerminalOutput<R> {
-    lines: LineStream<R>,
-}
-
-impl<R> TerminalOutput<R> {
-    fn new(lines: LineStream<R>) -> Self {
-        Self { lines }
-    }
-
-    fn next(&mut self) -> Option<Log>
-    where
-        R: BufRead,
-    {
-        self.lines.next().map(Log::from_line)
-    }
-}
-
-#[derive(PartialEq, Eq, Debug)]
-enum Log {
-    ToRoot,
-    ToChild,
-    ToParent,
-    Ls,
-    Directory,
-    File(u64),
-}
-
-impl Log {
-    fn from_line(line: &[u8]) -> Self {
-        if line.starts_with(b"$") {
-            let command = &line[2..];
-            if command == b"ls" {
-                Log::Ls
-            } else {
-                assert!(command.starts_with(b"cd"));
-                let to = &command[3..];
-                match to {
-                    b"/" => Log::ToRoot,
-                    b".." => Log::ToParent,
-                    _ => Log::ToChild,
-                }
-            }
-        } else if line.starts_with(b"dir") {
-            Log::Directory
-        } else {
-            let (size, _num_bytes) = u64::from_radix_10_checked(line);
-            Log::File(size.expect("Expected file size. Found {line}"))
-        }
-    }
+fn accumulated_directory_size(input: impl BufRead) -> Result<u64, InvalidTranscript> {
+    let size = FileSystem::from_transcript(input)?
+        .fold(SizeSum::default().filter(|size| size <= 100_000))
+        .into_inner()
+        .sum();
+    Ok(size)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{accumulated_directory_size, lines::LineStream, Log, TerminalOutput};
-
-    const TERMINAL_OUTPUT: &[u8] = r#"$ cd /
-$ ls
-dir a
-14848514 b.txt
-8504156 c.dat
-dir d
-$ cd a
-$ ls
-dir e
-29116 f
-2557 g
-62596 h.lst
-$ cd e
-$ ls
-584 i
-$ cd ..
-$ cd ..
-$ cd d
-$ ls
-4060174 j
-8033020 d.log
-5626152 d.ext
-7214296 k"#
-        .as_bytes();
+    use crate::accumulated_directory_size;
 
-    #[test]
-    fn parse_terminal_output() {
-        let input = LineStream::new(TERMINAL_OUTPUT);
-
-        let mut to = TerminalOutput::new(input);
-        assert_eq!(Some(Log::ToRoot), to.next());
-        assert_eq!(Some(Log::Ls), to.next());
-        assert_eq!(Some(Log::Directory), to.next());
-        assert_eq!(Some(Log::File(14848514)), to.next());
-        assert_eq!(Some(Log::File(8504156)), to.next());
-        assert_eq!(Some(Log::Directory), to.next());
-        assert_eq!(Some(Log::ToChild), to.next());
-        assert_eq!(Some(Log::Ls), to.next());
-        assert_eq!(Some(Log::Directory), to.next());
-        assert_eq!(Some(Log::File(29116)), to.next());
-        assert_eq!(Some(Log::File(2557)), to.next());
-        assert_eq!(Some(Log::File(62596)), to.next());
-        assert_eq!(Some(Log::ToChild), to.next());
-        assert_eq!(Some(Log::Ls), to.next());
-        assert_eq!(Some(Log::File(584)), to.next());
-        assert_eq!(Some(Log::ToParent), to.next());
-        assert_eq!(Some(Log::ToParent), to.next());
-        assert_eq!(Some(Log::ToChild), to.next());
-        assert_eq!(Some(Log::Ls), to.next());
-        assert_eq!(Some(Log::File(4060174)), to.next());
-        assert_eq!(Some(Log::File(8033020)), to.next());
-        assert_eq!(Some(Log::File(5626152)), to.next());
-        assert_eq!(Some(Log::File(7214296)), to.next());
-        assert!(to.next().is_none());
-    }
+    const TERMINAL_OUTPUT: &[u8] = include_bytes!("../../day-7-common/terminal_output.txt");
 
     #[test]
     fn accumulate_directory_sizes() {
-        assert_eq!(95437, accumulated_directory_size(TERMINAL_OUTPUT));
+        assert_eq!(95437, accumulated_directory_size(TERMINAL_OUTPUT).unwrap());
     }
 }
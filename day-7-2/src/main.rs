@@ -124,235 +124,81 @@
 //! unused space by 24933642. Find the smallest directory that, if deleted, would free up enough
 //! space on the filesystem to run the update. **What is the total size of that directory?**
 
-use atoi::FromRadix10Checked;
-use lines::LineStream;
-use std::{
-    fs::{self},
-    io::BufRead,
-};
-
-mod lines;
-
-const TOTAL_SIZE: u64 = 70_000_000;
-const REQUIRED_SIZE: u64 = 30_000_000;
-
-fn main() {
-    let input = fs::read("input.txt").expect("Can not open input file");
-    let size = size_of_directory_to_delete(&input);
-    println!("{size}");
-}
-
-fn size_of_directory_to_delete(input: &[u8]) -> u64 {
-    let lines = LineStream::new(input);
-    let mut terminal_output = TerminalOutput::new(lines);
-    let first_command = terminal_output.next();
-    assert_eq!(Some(Log::ToRoot), first_command);
-    let used = fold_directory_tree(&mut terminal_output, TotalSize(0)).0;
-    let free = TOTAL_SIZE - used;
-    let min_size = REQUIRED_SIZE - free;
-    let lines = LineStream::new(input);
-    let mut terminal_output = TerminalOutput::new(lines);
-    let first_command = terminal_output.next();
-    assert_eq!(Some(Log::ToRoot), first_command);
-    fold_directory_tree(
-        &mut terminal_output,
-        BestDeletionCandidate {
-            min_size,
-            best_size_so_far: u64::MAX,
-            current_dir_size: 0,
-        },
-    )
-    .best_size_so_far
-}
-
-pub trait Accumulator: Copy {
-    fn add_file(&mut self, size: u64);
-    fn add_child(&mut self, other: Self);
-    fn finalize_dir(&mut self);
-}
-
-#[derive(Clone, Copy)]
-struct TotalSize(u64);
-
-impl Accumulator for TotalSize {
-    fn add_file(&mut self, size: u64) {
-        self.0 += size
-    }
-
-    fn add_child(&mut self, other: Self) {
-        self.0 += other.0
-    }
-
-    fn finalize_dir(&mut self) {}
-}
-
-#[derive(Clone, Copy)]
-struct BestDeletionCandidate {
-    min_size: u64,
-    best_size_so_far: u64,
-    current_dir_size: u64,
-}
-
-impl Accumulator for BestDeletionCandidate {
-    fn add_file(&mut self, size: u64) {
-        self.current_dir_size += size;
-    }
-
-    fn add_child(&mut self, other: Self) {
-        if other.best_size_so_far < self.best_size_so_far {
-            self.best_size_so_far = other.best_size_so_far;
+use anyhow::{Context, Error};
+use day_7_common::{DirectorySizes, FileSystem, InvalidTranscript};
+use std::{env, fs};
+
+/// Disk space available to the file system, unless specified as first argument
+const DEFAULT_TOTAL_SIZE: u64 = 70_000_000;
+/// Unused space required by the update, unless specified as second argument
+const DEFAULT_REQUIRED_SIZE: u64 = 30_000_000;
+
+fn main() -> Result<(), Error> {
+    let mut args = env::args().skip(1).map(|arg| {
+        arg.parse::<u64>()
+            .context("Disk sizes must be non-negative integers")
+    });
+    let total = args.next().transpose()?.unwrap_or(DEFAULT_TOTAL_SIZE);
+    let required = args.next().transpose()?.unwrap_or(DEFAULT_REQUIRED_SIZE);
+    let input = fs::read("input.txt").context("Can not open input file")?;
+    match size_of_directory_to_delete(&input, total, required)? {
+        Some(0) => {
+            eprintln!("There is already enough unused space, nothing needs to be deleted");
+            println!("0");
         }
-        self.current_dir_size += other.current_dir_size;
+        Some(size) => println!("{size}"),
+        None => eprintln!("Deleting a single directory does not free up enough space"),
     }
-
-    fn finalize_dir(&mut self) {
-        if self.current_dir_size > self.min_size && self.current_dir_size < self.best_size_so_far {
-            self.best_size_so_far = self.current_dir_size;
-        }
-    }
-}
-
-// The text does not specify this, but the input is a straight forward depth first search, so we
-// won't keep track of any directory names
-fn fold_directory_tree<Acc: Accumulator>(to: &mut TerminalOutput<impl BufRead>, init: Acc) -> Acc {
-    let list = to.next();
-    assert_eq!(Some(Log::Ls), list);
-    let mut acc = init;
-    while let Some(log) = to.next() {
-        match log {
-            Log::ToRoot | Log::Ls => panic!("yagni"),
-            Log::ToChild => {
-                let child_acc = fold_directory_tree(to, init);
-                acc.add_child(child_acc);
-            }
-            Log::ToParent => break,
-            Log::Directory => (),
-            Log::File(size) => acc.add_file(size),
-        }
-    }
-    acc.finalize_dir();
-    acc
-}
-
-struct TerminalOutput<R> {
-    lines: LineStream<R>,
-}
-
-impl<R> TerminalOutput<R> {
-    fn new(lines: LineStream<R>) -> Self {
-        Self { lines }
-    }
-
-    fn next(&mut self) -> Option<Log>
-    where
-        R: BufRead,
-    {
-        self.lines.next().map(Log::from_line)
-    }
-}
-
-#[derive(PartialEq, Eq, Debug)]
-enum Log {
-    ToRoot,
-    ToChild,
-    ToParent,
-    Ls,
-    Directory,
-    File(u64),
+    Ok(())
 }
 
-impl Log {
-    fn from_line(line: &[u8]) -> Self {
-        if line.starts_with(b"$") {
-            let command = &line[2..];
-            if command == b"ls" {
-                Log::Ls
-            } else {
-                assert!(command.starts_with(b"cd"));
-                let to = &command[3..];
-                match to {
-                    b"/" => Log::ToRoot,
-                    b".." => Log::ToParent,
-                    _ => Log::ToChild,
-                }
-            }
-        } else if line.starts_with(b"dir") {
-            Log::Directory
-        } else {
-            let (size, _num_bytes) = u64::from_radix_10_checked(line);
-            Log::File(size.expect("Expected file size. Found {line}"))
-        }
+/// Smallest directory, which frees up enough space to have `required` unused space on a disk of
+/// size `total`, if deleted. `Some(0)` if there is already enough unused space, since nothing needs
+/// to be deleted then.
+fn size_of_directory_to_delete(
+    input: &[u8],
+    total: u64,
+    required: u64,
+) -> Result<Option<u64>, InvalidTranscript> {
+    let sizes = DirectorySizes::new(&FileSystem::from_transcript(input)?);
+    let to_free = sizes.space_to_free(total, required);
+    if to_free == 0 {
+        return Ok(Some(0));
     }
+    Ok(sizes.smallest_at_least(to_free))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{fold_directory_tree, lines::LineStream, Log, TerminalOutput, TotalSize};
+    use crate::{size_of_directory_to_delete, DEFAULT_REQUIRED_SIZE, DEFAULT_TOTAL_SIZE};
 
-    const TERMINAL_OUTPUT: &[u8] = r#"$ cd /
-$ ls
-dir a
-14848514 b.txt
-8504156 c.dat
-dir d
-$ cd a
-$ ls
-dir e
-29116 f
-2557 g
-62596 h.lst
-$ cd e
-$ ls
-584 i
-$ cd ..
-$ cd ..
-$ cd d
-$ ls
-4060174 j
-8033020 d.log
-5626152 d.ext
-7214296 k"#
-        .as_bytes();
+    const TERMINAL_OUTPUT: &[u8] = include_bytes!("../../day-7-common/terminal_output.txt");
 
     #[test]
-    fn parse_terminal_output() {
-        let input = LineStream::new(TERMINAL_OUTPUT);
-
-        let mut to = TerminalOutput::new(input);
-        assert_eq!(Some(Log::ToRoot), to.next());
-        assert_eq!(Some(Log::Ls), to.next());
-        assert_eq!(Some(Log::Directory), to.next());
-        assert_eq!(Some(Log::File(14848514)), to.next());
-        assert_eq!(Some(Log::File(8504156)), to.next());
-        assert_eq!(Some(Log::Directory), to.next());
-        assert_eq!(Some(Log::ToChild), to.next());
-        assert_eq!(Some(Log::Ls), to.next());
-        assert_eq!(Some(Log::Directory), to.next());
-        assert_eq!(Some(Log::File(29116)), to.next());
-        assert_eq!(Some(Log::File(2557)), to.next());
-        assert_eq!(Some(Log::File(62596)), to.next());
-        assert_eq!(Some(Log::ToChild), to.next());
-        assert_eq!(Some(Log::Ls), to.next());
-        assert_eq!(Some(Log::File(584)), to.next());
-        assert_eq!(Some(Log::ToParent), to.next());
-        assert_eq!(Some(Log::ToParent), to.next());
-        assert_eq!(Some(Log::ToChild), to.next());
-        assert_eq!(Some(Log::Ls), to.next());
-        assert_eq!(Some(Log::File(4060174)), to.next());
-        assert_eq!(Some(Log::File(8033020)), to.next());
-        assert_eq!(Some(Log::File(5626152)), to.next());
-        assert_eq!(Some(Log::File(7214296)), to.next());
-        assert!(to.next().is_none());
+    fn directory_to_delete() {
+        assert_eq!(
+            Some(24933642),
+            size_of_directory_to_delete(TERMINAL_OUTPUT, DEFAULT_TOTAL_SIZE, DEFAULT_REQUIRED_SIZE)
+                .unwrap()
+        );
     }
 
     #[test]
-    fn total_dir_size() {
-        let input = LineStream::new(TERMINAL_OUTPUT);
-        let mut to = TerminalOutput::new(input);
-        to.next();
-
-        let actual = fold_directory_tree(&mut to, TotalSize(0));
-
-        assert_eq!(48381165, actual.0);
+    fn other_disk_sizes() {
+        // Only deleting everything frees up enough space
+        assert_eq!(
+            Some(48381165),
+            size_of_directory_to_delete(TERMINAL_OUTPUT, 50_000_000, 30_000_000).unwrap()
+        );
+        // Not even deleting everything would be enough
+        assert_eq!(
+            None,
+            size_of_directory_to_delete(TERMINAL_OUTPUT, 50_000_000, 50_000_001).unwrap()
+        );
+        // Enough unused space already, so not even the smallest directory must be deleted
+        assert_eq!(
+            Some(0),
+            size_of_directory_to_delete(TERMINAL_OUTPUT, 100_000_000, 30_000_000).unwrap()
+        );
     }
 }
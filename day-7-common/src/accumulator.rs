@@ -0,0 +1,274 @@
+//! Folding the directory tree bottom up. Accumulators can be combined, in order to answer several
+//! questions about the file system within a single traversal.
+
+/// Computes a value for each directory from its files and the values of its sub directories. See
+/// [`crate::FileSystem::fold`].
+///
+/// Each directory starts out with a clone of the initial accumulator, which is why it needs to be
+/// `Clone`. Accumulators owning heap allocations (like [`Sizes`]) are fine, as long as the initial
+/// one is cheap to clone.
+pub trait Accumulator: Clone {
+    fn add_file(&mut self, size: u64);
+    fn add_child(&mut self, other: Self);
+    fn finalize_dir(&mut self);
+
+    /// Feeds the size of each file through `f`, before adding it. E.g. `|_| 1` counts files instead
+    /// of bytes.
+    fn map<F>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: Fn(u64) -> u64 + Clone,
+    {
+        Map { inner: self, f }
+    }
+
+    /// Only finalizes directories whose total size satisfies `predicate`. Files and sub directories
+    /// of skipped directories are still added.
+    ///
+    /// The total size is made up of the file sizes as they reach the filter. So in
+    /// `acc.map(f).filter(p)` the predicate sees the original sizes, while in
+    /// `acc.filter(p).map(f)` it sees the mapped ones.
+    fn filter<P>(self, predicate: P) -> Filter<Self, P>
+    where
+        Self: Sized,
+        P: Fn(u64) -> bool + Clone,
+    {
+        Filter {
+            inner: self,
+            predicate,
+            size: 0,
+        }
+    }
+}
+
+/// See [`Accumulator::map`].
+#[derive(Debug, Clone)]
+pub struct Map<A, F> {
+    inner: A,
+    f: F,
+}
+
+impl<A, F> Map<A, F> {
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+}
+
+impl<A, F> Accumulator for Map<A, F>
+where
+    A: Accumulator,
+    F: Fn(u64) -> u64 + Clone,
+{
+    fn add_file(&mut self, size: u64) {
+        self.inner.add_file((self.f)(size))
+    }
+
+    fn add_child(&mut self, other: Self) {
+        self.inner.add_child(other.inner)
+    }
+
+    fn finalize_dir(&mut self) {
+        self.inner.finalize_dir()
+    }
+}
+
+/// See [`Accumulator::filter`].
+#[derive(Debug, Clone)]
+pub struct Filter<A, P> {
+    inner: A,
+    predicate: P,
+    /// Total size of the current directory, which is checked against the predicate
+    size: u64,
+}
+
+impl<A, P> Filter<A, P> {
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+}
+
+impl<A, P> Accumulator for Filter<A, P>
+where
+    A: Accumulator,
+    P: Fn(u64) -> bool + Clone,
+{
+    fn add_file(&mut self, size: u64) {
+        self.size += size;
+        self.inner.add_file(size)
+    }
+
+    fn add_child(&mut self, other: Self) {
+        self.size += other.size;
+        self.inner.add_child(other.inner)
+    }
+
+    fn finalize_dir(&mut self) {
+        if (self.predicate)(self.size) {
+            self.inner.finalize_dir()
+        }
+    }
+}
+
+/// Runs several accumulators side by side.
+macro_rules! impl_accumulator_for_tuple {
+    ($($acc:ident $index:tt),+) => {
+        impl<$($acc: Accumulator),+> Accumulator for ($($acc,)+) {
+            fn add_file(&mut self, size: u64) {
+                $(self.$index.add_file(size);)+
+            }
+
+            fn add_child(&mut self, other: Self) {
+                $(self.$index.add_child(other.$index);)+
+            }
+
+            fn finalize_dir(&mut self) {
+                $(self.$index.finalize_dir();)+
+            }
+        }
+    };
+}
+
+impl_accumulator_for_tuple!(A 0, B 1);
+impl_accumulator_for_tuple!(A 0, B 1, C 2);
+impl_accumulator_for_tuple!(A 0, B 1, C 2, D 3);
+
+/// Total size of the directory.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TotalSize(pub u64);
+
+impl Accumulator for TotalSize {
+    fn add_file(&mut self, size: u64) {
+        self.0 += size
+    }
+
+    fn add_child(&mut self, other: Self) {
+        self.0 += other.0
+    }
+
+    fn finalize_dir(&mut self) {}
+}
+
+/// Sum of the total sizes of all finalized directories. Files contained in nested directories
+/// count once for each of them.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SizeSum {
+    size: u64,
+    sum: u64,
+}
+
+impl SizeSum {
+    pub fn sum(&self) -> u64 {
+        self.sum
+    }
+}
+
+impl Accumulator for SizeSum {
+    fn add_file(&mut self, size: u64) {
+        self.size += size
+    }
+
+    fn add_child(&mut self, other: Self) {
+        self.size += other.size;
+        self.sum += other.sum;
+    }
+
+    fn finalize_dir(&mut self) {
+        self.sum += self.size
+    }
+}
+
+/// Collects the total sizes of all finalized directories, in the order they are finalized. A
+/// directory always comes after its sub directories.
+#[derive(Debug, Default, Clone)]
+pub struct Sizes {
+    size: u64,
+    sizes: Vec<u64>,
+}
+
+impl Sizes {
+    pub fn into_sizes(self) -> Vec<u64> {
+        self.sizes
+    }
+}
+
+impl Accumulator for Sizes {
+    fn add_file(&mut self, size: u64) {
+        self.size += size
+    }
+
+    fn add_child(&mut self, other: Self) {
+        self.size += other.size;
+        self.sizes.extend(other.sizes);
+    }
+
+    fn finalize_dir(&mut self) {
+        self.sizes.push(self.size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FileSystem, TERMINAL_OUTPUT};
+
+    use super::{Accumulator, SizeSum, Sizes, TotalSize};
+
+    #[test]
+    fn both_parts_in_one_traversal() {
+        let fs = FileSystem::from_transcript(TERMINAL_OUTPUT).unwrap();
+
+        let (total, small, sizes) = fs.fold((
+            TotalSize::default(),
+            SizeSum::default().filter(|size| size <= 100_000),
+            Sizes::default(),
+        ));
+
+        assert_eq!(48381165, total.0);
+        assert_eq!(95437, small.into_inner().sum());
+        let min_size = 30_000_000 - (70_000_000 - total.0);
+        let best_deletion_candidate = sizes
+            .into_sizes()
+            .into_iter()
+            .filter(|&size| size >= min_size)
+            .min();
+        assert_eq!(Some(24933642), best_deletion_candidate);
+    }
+
+    #[test]
+    fn sizes_in_post_order() {
+        let fs = FileSystem::from_transcript(TERMINAL_OUTPUT).unwrap();
+
+        let sizes = fs.fold(Sizes::default()).into_sizes();
+
+        assert_eq!(vec![584, 94853, 24933642, 48381165], sizes);
+    }
+
+    #[test]
+    fn count_files() {
+        let fs = FileSystem::from_transcript(TERMINAL_OUTPUT).unwrap();
+
+        let num_files = fs.fold(TotalSize::default().map(|_| 1));
+
+        assert_eq!(TotalSize(10), num_files.into_inner());
+    }
+
+    #[test]
+    fn filter_after_map_sees_original_sizes() {
+        let fs = FileSystem::from_transcript(TERMINAL_OUTPUT).unwrap();
+
+        // Number of files in directories of at most 100000 bytes. `i` is counted for both `e` and
+        // `a`.
+        let num_files = fs.fold(SizeSum::default().map(|_| 1).filter(|size| size <= 100_000));
+
+        assert_eq!(5, num_files.into_inner().into_inner().sum());
+    }
+
+    #[test]
+    fn filter_before_map_sees_mapped_sizes() {
+        let fs = FileSystem::from_transcript(TERMINAL_OUTPUT).unwrap();
+
+        // Number of files in directories with at most 4 files: `e` (1), `a` (4) and `d` (4)
+        let num_files = fs.fold(SizeSum::default().filter(|count| count <= 4).map(|_| 1));
+
+        assert_eq!(9, num_files.into_inner().into_inner().sum());
+    }
+}
@@ -0,0 +1,224 @@
+//! Directories and files discovered by browsing the device, including their names.
+
+use std::{fmt, io::BufRead};
+
+use common::LineStream;
+
+use crate::{Accumulator, Interpreter, InvalidTranscript, TerminalOutput};
+
+/// Tree of named directories and files, as revealed by the terminal output.
+#[derive(Debug, Default)]
+pub struct FileSystem {
+    root: Directory,
+}
+
+impl FileSystem {
+    /// Reconstructs the file system from the `cd` and `ls` commands in the terminal output. See
+    /// [`Interpreter`] for the kinds of transcripts which are understood.
+    pub fn from_transcript(input: impl BufRead) -> Result<Self, InvalidTranscript> {
+        let mut interpreter = Interpreter::new();
+        for (index, log) in TerminalOutput::new(LineStream::new(input)).enumerate() {
+            let log = log.map_err(|reason| InvalidTranscript {
+                line: index + 1,
+                reason,
+            })?;
+            interpreter.execute(log)?;
+        }
+        Ok(interpreter.into_file_system())
+    }
+
+    /// The outermost directory `/`.
+    pub fn root(&self) -> &Directory {
+        &self.root
+    }
+
+    pub(crate) fn root_mut(&mut self) -> &mut Directory {
+        &mut self.root
+    }
+
+    /// Folds all directories bottom up, starting with `init` for each of them.
+    pub fn fold<Acc: Accumulator>(&self, init: Acc) -> Acc {
+        self.root.fold(init)
+    }
+
+    /// Looks up a file or directory by its absolute path, e.g. `/a/e`. `/` is the root directory.
+    pub fn get(&self, path: &str) -> Option<Entry<'_>> {
+        let path = path.strip_prefix('/')?;
+        let mut current = Entry::Directory(&self.root);
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            current = match current {
+                Entry::Directory(dir) => dir.get(name)?,
+                Entry::File(_) => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Total size of the file or directory at the path.
+    pub fn size(&self, path: &str) -> Option<u64> {
+        self.get(path).map(|entry| entry.size())
+    }
+}
+
+/// Prints the file system as indented tree, like the puzzle description does.
+impl fmt::Display for FileSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "- / (dir)")?;
+        self.root.fmt_children(f, 1)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Directory {
+    /// Sub directories and files in the order they have been listed.
+    entries: Vec<(String, Node)>,
+}
+
+#[derive(Debug)]
+enum Node {
+    File(u64),
+    Directory(Directory),
+}
+
+/// A file or a directory within the [`FileSystem`].
+#[derive(Debug, Clone, Copy)]
+pub enum Entry<'a> {
+    /// Size of the file
+    File(u64),
+    Directory(&'a Directory),
+}
+
+impl Entry<'_> {
+    /// Size of a file, or total size of all files contained in a directory.
+    pub fn size(&self) -> u64 {
+        match self {
+            Entry::File(size) => *size,
+            Entry::Directory(dir) => dir.total_size(),
+        }
+    }
+}
+
+impl Directory {
+    /// Sum of the sizes of all files contained directly or indirectly.
+    pub fn total_size(&self) -> u64 {
+        self.entries().map(|(_, entry)| entry.size()).sum()
+    }
+
+    /// Sub directories and files directly contained, in the order they have been listed.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, Entry<'_>)> {
+        self.entries.iter().map(|(name, node)| {
+            let entry = match node {
+                Node::File(size) => Entry::File(*size),
+                Node::Directory(dir) => Entry::Directory(dir),
+            };
+            (name.as_str(), entry)
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<Entry<'_>> {
+        self.entries()
+            .find(|(entry_name, _)| *entry_name == name)
+            .map(|(_, entry)| entry)
+    }
+
+    /// Folds this directory and all of its sub directories bottom up, starting with `init` for
+    /// each of them.
+    pub fn fold<Acc: Accumulator>(&self, init: Acc) -> Acc {
+        let mut acc = init.clone();
+        for (_, entry) in self.entries() {
+            match entry {
+                Entry::File(size) => acc.add_file(size),
+                Entry::Directory(dir) => acc.add_child(dir.fold(init.clone())),
+            }
+        }
+        acc.finalize_dir();
+        acc
+    }
+
+    /// Sub directory with the given name. Created if it has not been listed yet. `None` if the name
+    /// has been listed as a file.
+    pub(crate) fn child_mut(&mut self, name: &str) -> Option<&mut Directory> {
+        let pos = match self.entries.iter().position(|(n, _)| n == name) {
+            Some(pos) => pos,
+            None => {
+                self.entries
+                    .push((name.to_owned(), Node::Directory(Directory::default())));
+                self.entries.len() - 1
+            }
+        };
+        match &mut self.entries[pos].1 {
+            Node::Directory(dir) => Some(dir),
+            Node::File(_) => None,
+        }
+    }
+
+    /// Adds a file, or updates its size if it has already been listed. `false`, without changing
+    /// anything, if the name has been listed as a directory.
+    pub(crate) fn insert_file(&mut self, name: String, size: u64) -> bool {
+        match self.entries.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, Node::Directory(_))) => return false,
+            Some((_, node)) => *node = Node::File(size),
+            None => self.entries.push((name, Node::File(size))),
+        }
+        true
+    }
+
+    fn fmt_children(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let indent = "  ".repeat(depth);
+        for (name, entry) in self.entries() {
+            match entry {
+                Entry::File(size) => writeln!(f, "{indent}- {name} (file, size={size})")?,
+                Entry::Directory(dir) => {
+                    writeln!(f, "{indent}- {name} (dir)")?;
+                    dir.fmt_children(f, depth + 1)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TERMINAL_OUTPUT;
+
+    use super::{Entry, FileSystem};
+
+    #[test]
+    fn print_tree() {
+        let fs = FileSystem::from_transcript(TERMINAL_OUTPUT).unwrap();
+
+        let expected = "\
+            - / (dir)\n  \
+              - a (dir)\n    \
+                - e (dir)\n      \
+                  - i (file, size=584)\n    \
+                - f (file, size=29116)\n    \
+                - g (file, size=2557)\n    \
+                - h.lst (file, size=62596)\n  \
+              - b.txt (file, size=14848514)\n  \
+              - c.dat (file, size=8504156)\n  \
+              - d (dir)\n    \
+                - j (file, size=4060174)\n    \
+                - d.log (file, size=8033020)\n    \
+                - d.ext (file, size=5626152)\n    \
+                - k (file, size=7214296)\n\
+        ";
+        assert_eq!(expected, fs.to_string());
+    }
+
+    #[test]
+    fn query_by_path() {
+        let fs = FileSystem::from_transcript(TERMINAL_OUTPUT).unwrap();
+
+        assert_eq!(Some(584), fs.size("/a/e"));
+        assert_eq!(Some(94853), fs.size("/a"));
+        assert_eq!(Some(24933642), fs.size("/d/"));
+        assert_eq!(Some(48381165), fs.size("/"));
+        assert_eq!(Some(62596), fs.size("/a/h.lst"));
+        assert!(matches!(fs.get("/a/f"), Some(Entry::File(29116))));
+        assert!(fs.get("/a/x").is_none());
+        assert!(fs.get("/b.txt/x").is_none());
+        assert!(fs.get("a").is_none());
+    }
+}
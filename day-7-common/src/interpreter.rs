@@ -0,0 +1,257 @@
+//! Replays the terminal output like a shell would, keeping track of the current directory.
+
+use std::{error::Error, fmt};
+
+use crate::{filesystem::Directory, FileSystem, Log};
+
+/// Reason why a line of the transcript is malformed or contradicts the lines before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidLog {
+    /// Line is neither a `cd` or `ls` command, nor a `dir <name>` or `<size> <name>` entry.
+    Malformed(String),
+    /// `cd` into or `dir` listing of a path which has been listed as a file.
+    NotADirectory(String),
+    /// A file listed at a path which has been listed as a directory.
+    NotAFile(String),
+}
+
+impl fmt::Display for InvalidLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidLog::Malformed(line) => write!(
+                f,
+                "expected `$ cd <name>`, `$ ls`, `dir <name>` or `<size> <name>`, found {line:?}"
+            ),
+            InvalidLog::NotADirectory(path) => write!(f, "{path} has been listed as a file"),
+            InvalidLog::NotAFile(path) => write!(f, "{path} has been listed as a directory"),
+        }
+    }
+}
+
+impl Error for InvalidLog {}
+
+/// An invalid log, together with the line it originated from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidTranscript {
+    /// Line number, starting with `1`.
+    pub line: usize,
+    pub reason: InvalidLog,
+}
+
+impl fmt::Display for InvalidTranscript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid terminal output in line {}: {}",
+            self.line, self.reason
+        )
+    }
+}
+
+impl Error for InvalidTranscript {}
+
+/// Interprets the `cd` and `ls` commands of a transcript one at a time. In contrast to a strict
+/// depth first walk, the session may jump back to the root, revisit directories and list the same
+/// directory more than once. Entries listed again replace the ones listed before, so nothing is
+/// counted twice.
+#[derive(Debug, Default)]
+pub struct Interpreter {
+    file_system: FileSystem,
+    /// Names of the directories from the root down to the current one
+    cwd: Vec<String>,
+    /// Number of logs executed so far, i.e. the line of the last one
+    line: usize,
+}
+
+impl Interpreter {
+    /// Starts at the root of an empty file system.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the log of the next line. Fails, without changing anything, if the log contradicts
+    /// what has been learned about the file system before, e.g. changing into a file.
+    pub fn execute(&mut self, log: Log) -> Result<(), InvalidTranscript> {
+        self.line += 1;
+        match log {
+            Log::ToRoot => self.cwd.clear(),
+            // Like in a real shell, the parent of the root is the root itself
+            Log::ToParent => {
+                self.cwd.pop();
+            }
+            Log::ToChild(name) => {
+                if self.current_dir().child_mut(&name).is_none() {
+                    return Err(self.error(InvalidLog::NotADirectory(self.path_of(&name))));
+                }
+                self.cwd.push(name);
+            }
+            Log::Ls => (),
+            Log::Directory(name) => {
+                if self.current_dir().child_mut(&name).is_none() {
+                    return Err(self.error(InvalidLog::NotADirectory(self.path_of(&name))));
+                }
+            }
+            Log::File { size, name } => {
+                let path = self.path_of(&name);
+                if !self.current_dir().insert_file(name, size) {
+                    return Err(self.error(InvalidLog::NotAFile(path)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Absolute path of the current directory, e.g. `/a/e`.
+    pub fn cwd(&self) -> String {
+        if self.cwd.is_empty() {
+            "/".to_owned()
+        } else {
+            self.cwd.iter().map(|name| format!("/{name}")).collect()
+        }
+    }
+
+    /// Everything learned about the file system so far.
+    pub fn file_system(&self) -> &FileSystem {
+        &self.file_system
+    }
+
+    pub fn into_file_system(self) -> FileSystem {
+        self.file_system
+    }
+
+    /// Absolute path of an entry in the current directory
+    fn path_of(&self, name: &str) -> String {
+        let mut path: String = self.cwd.iter().map(|name| format!("/{name}")).collect();
+        path.push('/');
+        path.push_str(name);
+        path
+    }
+
+    fn error(&self, reason: InvalidLog) -> InvalidTranscript {
+        InvalidTranscript {
+            line: self.line,
+            reason,
+        }
+    }
+
+    fn current_dir(&mut self) -> &mut Directory {
+        // Entries listed as directories are never replaced by files, so every directory on the way
+        // down to the current one still exists.
+        self.cwd
+            .iter()
+            .fold(self.file_system.root_mut(), |dir, name| {
+                dir.child_mut(name)
+                    .expect("Current directory has been entered before")
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interpreter, InvalidLog, InvalidTranscript};
+    use crate::{FileSystem, Log};
+
+    #[test]
+    fn track_current_directory() {
+        let mut interpreter = Interpreter::new();
+        assert_eq!("/", interpreter.cwd());
+
+        interpreter.execute(Log::ToChild("a".to_owned())).unwrap();
+        interpreter.execute(Log::ToChild("e".to_owned())).unwrap();
+        assert_eq!("/a/e", interpreter.cwd());
+        interpreter.execute(Log::ToParent).unwrap();
+        assert_eq!("/a", interpreter.cwd());
+        interpreter.execute(Log::ToRoot).unwrap();
+        assert_eq!("/", interpreter.cwd());
+        interpreter.execute(Log::ToParent).unwrap();
+        assert_eq!("/", interpreter.cwd());
+    }
+
+    #[test]
+    fn revisits_do_not_double_count() {
+        // Same device as in the puzzle description, but browsed in a less orderly fashion
+        let input = b"\
+            $ ls\n\
+            dir a\n\
+            14848514 b.txt\n\
+            $ cd a\n\
+            $ cd e\n\
+            $ ls\n\
+            584 i\n\
+            $ cd /\n\
+            $ ls\n\
+            dir a\n\
+            14848514 b.txt\n\
+            8504156 c.dat\n\
+            dir d\n\
+            $ cd d\n\
+            $ ls\n\
+            4060174 j\n\
+            8033020 d.log\n\
+            5626152 d.ext\n\
+            7214296 k\n\
+            $ cd ..\n\
+            $ cd a\n\
+            $ ls\n\
+            dir e\n\
+            29116 f\n\
+            2557 g\n\
+            62596 h.lst\n\
+            $ ls\n\
+            dir e\n\
+            29116 f\n\
+            2557 g\n\
+            62596 h.lst\n\
+            $ cd e\n\
+            $ ls\n\
+            584 i\n\
+        ";
+
+        let fs = FileSystem::from_transcript(&input[..]).unwrap();
+
+        assert_eq!(Some(584), fs.size("/a/e"));
+        assert_eq!(Some(94853), fs.size("/a"));
+        assert_eq!(Some(24933642), fs.size("/d"));
+        assert_eq!(Some(48381165), fs.size("/"));
+    }
+
+    #[test]
+    fn report_line_of_contradicting_log() {
+        let into_file = b"$ cd /\n$ ls\n14848514 b.txt\n$ cd b.txt\n";
+        let file_over_dir = b"$ ls\ndir a\n$ cd a\n$ ls\ndir e\n$ cd ..\n$ cd a\n$ ls\n584 e\n";
+
+        let error = FileSystem::from_transcript(&into_file[..]).unwrap_err();
+
+        assert_eq!(
+            InvalidTranscript {
+                line: 4,
+                reason: InvalidLog::NotADirectory("/b.txt".to_owned())
+            },
+            error
+        );
+        assert_eq!(
+            "Invalid terminal output in line 4: /b.txt has been listed as a file",
+            error.to_string()
+        );
+        assert_eq!(
+            InvalidTranscript {
+                line: 9,
+                reason: InvalidLog::NotAFile("/a/e".to_owned())
+            },
+            FileSystem::from_transcript(&file_over_dir[..]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn report_line_of_malformed_log() {
+        let input = b"$ cd /\n$ ls\n123\n$ cd\n";
+
+        assert_eq!(
+            InvalidTranscript {
+                line: 3,
+                reason: InvalidLog::Malformed("123".to_owned())
+            },
+            FileSystem::from_transcript(&input[..]).unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Terminal output of Day 7: No Space Left On Device. Both parts browse the same transcript of `cd`
+//! and `ls` commands, in order to learn about the sizes of the directories on the device.
+
+use atoi::FromRadix10Checked;
+use common::LineStream;
+use std::io::BufRead;
+
+pub use self::{
+    accumulator::{Accumulator, Filter, Map, SizeSum, Sizes, TotalSize},
+    filesystem::{Directory, Entry, FileSystem},
+    interpreter::{Interpreter, InvalidLog, InvalidTranscript},
+    report::{
+        directories_at_most, files_with_extension, human_size, largest_directories, write_human,
+        Usage,
+    },
+    sizes::DirectorySizes,
+};
+
+mod accumulator;
+mod filesystem;
+mod interpreter;
+mod report;
+mod sizes;
+
+pub struct TerminalOutput<R> {
+    lines: LineStream<R>,
+}
+
+impl<R> TerminalOutput<R> {
+    pub fn new(lines: LineStream<R>) -> Self {
+        Self { lines }
+    }
+}
+
+impl<R> Iterator for TerminalOutput<R>
+where
+    R: BufRead,
+{
+    type Item = Result<Log, InvalidLog>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines.next_line().map(Log::from_line)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Log {
+    /// `$ cd /`
+    ToRoot,
+    /// `$ cd <name>`
+    ToChild(String),
+    /// `$ cd ..`
+    ToParent,
+    /// `$ ls`
+    Ls,
+    /// `dir <name>` listed by `ls`
+    Directory(String),
+    /// `<size> <name>` listed by `ls`
+    File { size: u64, name: String },
+}
+
+impl Log {
+    /// Fails with [`InvalidLog::Malformed`] if the line is neither a `cd` or `ls` command, nor an
+    /// entry listed by `ls`.
+    pub fn from_line(line: &[u8]) -> Result<Self, InvalidLog> {
+        let log = if let Some(command) = line.strip_prefix(b"$ ") {
+            if command == b"ls" {
+                Log::Ls
+            } else {
+                match command.strip_prefix(b"cd ") {
+                    Some(b"/") => Log::ToRoot,
+                    Some(b"..") => Log::ToParent,
+                    Some(to) if !to.is_empty() => Log::ToChild(name(to)),
+                    _ => return Err(malformed(line)),
+                }
+            }
+        } else if let Some(dir) = line.strip_prefix(b"dir ").filter(|dir| !dir.is_empty()) {
+            Log::Directory(name(dir))
+        } else {
+            let (size, num_bytes) = u64::from_radix_10_checked(line);
+            match (size, line[num_bytes..].strip_prefix(b" ")) {
+                (Some(size), Some(file)) if num_bytes > 0 && !file.is_empty() => Log::File {
+                    size,
+                    name: name(file),
+                },
+                _ => return Err(malformed(line)),
+            }
+        };
+        Ok(log)
+    }
+}
+
+fn malformed(line: &[u8]) -> InvalidLog {
+    InvalidLog::Malformed(name(line))
+}
+
+fn name(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Example transcript of the puzzle, shared by the tests of all modules.
+#[cfg(test)]
+pub(crate) const TERMINAL_OUTPUT: &[u8] = include_bytes!("../terminal_output.txt");
+
+#[cfg(test)]
+mod tests {
+    use common::LineStream;
+
+    use crate::{InvalidLog, Log, TerminalOutput, TERMINAL_OUTPUT};
+
+    #[test]
+    fn parse_terminal_output() {
+        let input = LineStream::new(TERMINAL_OUTPUT);
+        let file = |size, name: &str| {
+            Some(Log::File {
+                size,
+                name: name.to_owned(),
+            })
+        };
+        let dir = |name: &str| Some(Log::Directory(name.to_owned()));
+        let cd = |name: &str| Some(Log::ToChild(name.to_owned()));
+
+        let mut to = TerminalOutput::new(input).map(Result::unwrap);
+        assert_eq!(Some(Log::ToRoot), to.next());
+        assert_eq!(Some(Log::Ls), to.next());
+        assert_eq!(dir("a"), to.next());
+        assert_eq!(file(14848514, "b.txt"), to.next());
+        assert_eq!(file(8504156, "c.dat"), to.next());
+        assert_eq!(dir("d"), to.next());
+        assert_eq!(cd("a"), to.next());
+        assert_eq!(Some(Log::Ls), to.next());
+        assert_eq!(dir("e"), to.next());
+        assert_eq!(file(29116, "f"), to.next());
+        assert_eq!(file(2557, "g"), to.next());
+        assert_eq!(file(62596, "h.lst"), to.next());
+        assert_eq!(cd("e"), to.next());
+        assert_eq!(Some(Log::Ls), to.next());
+        assert_eq!(file(584, "i"), to.next());
+        assert_eq!(Some(Log::ToParent), to.next());
+        assert_eq!(Some(Log::ToParent), to.next());
+        assert_eq!(cd("d"), to.next());
+        assert_eq!(Some(Log::Ls), to.next());
+        assert_eq!(file(4060174, "j"), to.next());
+        assert_eq!(file(8033020, "d.log"), to.next());
+        assert_eq!(file(5626152, "d.ext"), to.next());
+        assert_eq!(file(7214296, "k"), to.next());
+        assert!(to.next().is_none());
+    }
+
+    #[test]
+    fn reject_malformed_lines() {
+        for line in [
+            "$ cd", "$ cd ", "$ dir a", "$ls", "dir ", "123", "123 ", "a.txt", "-5 a.txt",
+        ] {
+            assert_eq!(
+                Err(InvalidLog::Malformed(line.to_owned())),
+                Log::from_line(line.as_bytes()),
+                "{line}"
+            );
+        }
+        // Size does not fit into 64 bits
+        assert!(Log::from_line(b"18446744073709551616 a.txt").is_err());
+    }
+}
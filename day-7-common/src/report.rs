@@ -0,0 +1,180 @@
+//! Questions about disk usage, answered in the spirit of `du`. Each query yields paths along with
+//! their sizes, which can be printed for humans.
+
+use std::io::{self, Write};
+
+use crate::{Directory, Entry, FileSystem};
+
+/// Size of a file or the total size of a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Usage {
+    /// Absolute path, e.g. `/a/h.lst`
+    pub path: String,
+    pub size: u64,
+}
+
+/// The `n` largest directories, largest first. Directories of equal size are ordered by path.
+pub fn largest_directories(fs: &FileSystem, n: usize) -> Vec<Usage> {
+    let mut usages = directories(fs);
+    usages.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    usages.truncate(n);
+    usages
+}
+
+/// All directories with a total size of at most `max_size`, in the order they have been listed.
+pub fn directories_at_most(fs: &FileSystem, max_size: u64) -> Vec<Usage> {
+    let mut usages = directories(fs);
+    usages.retain(|usage| usage.size <= max_size);
+    usages
+}
+
+/// All files whose name ends in the extension, in the order they have been listed. The leading dot
+/// of the extension is optional, i.e. both `.dat` and `dat` match `c.dat`.
+pub fn files_with_extension(fs: &FileSystem, extension: &str) -> Vec<Usage> {
+    let suffix = format!(".{}", extension.trim_start_matches('.'));
+    let mut usages = Vec::new();
+    walk(fs.root(), "", &mut |path, entry| {
+        if let Entry::File(size) = entry {
+            if path.ends_with(&suffix) {
+                usages.push(Usage {
+                    path: path.to_owned(),
+                    size,
+                });
+            }
+        }
+    });
+    usages
+}
+
+/// One line per entry, size first and path second, separated by a tab. Just like `du -h`.
+pub fn write_human(usages: &[Usage], mut out: impl Write) -> io::Result<()> {
+    for usage in usages {
+        writeln!(out, "{}\t{}", human_size(usage.size), usage.path)?;
+    }
+    Ok(())
+}
+
+/// Size in powers of 1024, e.g. `93K` or `8.2M`. Like `du -h`, sizes are rounded up and shown with
+/// a single decimal if they are less than ten units.
+pub fn human_size(size: u64) -> String {
+    const UNITS: [char; 6] = ['K', 'M', 'G', 'T', 'P', 'E'];
+    if size < 1024 {
+        return size.to_string();
+    }
+    let mut unit_index = 0;
+    let mut unit = 1024u64;
+    // Choose the unit after rounding, so we print `1.0M` rather than `1024K`
+    while unit_index + 1 < UNITS.len() && size.div_ceil(unit) >= 1024 {
+        unit_index += 1;
+        unit *= 1024;
+    }
+    let suffix = UNITS[unit_index];
+    let tenths = (size as u128 * 10).div_ceil(unit as u128);
+    if tenths < 100 {
+        format!("{}.{}{suffix}", tenths / 10, tenths % 10)
+    } else {
+        format!("{}{suffix}", size.div_ceil(unit))
+    }
+}
+
+/// Every directory, including the root, along with its total size. Parents come before their
+/// children, in the order they have been listed.
+fn directories(fs: &FileSystem) -> Vec<Usage> {
+    let mut usages = Vec::new();
+    push_directories(fs.root(), "", &mut usages);
+    usages
+}
+
+/// Pushes the usage of `dir` followed by the usages of all directories below it. Sizes are summed
+/// up on the way back, so each directory is only visited once. Returns the total size of `dir`.
+fn push_directories(dir: &Directory, path: &str, usages: &mut Vec<Usage>) -> u64 {
+    let index = usages.len();
+    usages.push(Usage {
+        path: if path.is_empty() { "/" } else { path }.to_owned(),
+        size: 0,
+    });
+    let mut size = 0;
+    for (name, entry) in dir.entries() {
+        size += match entry {
+            Entry::File(file_size) => file_size,
+            Entry::Directory(child) => push_directories(child, &format!("{path}/{name}"), usages),
+        };
+    }
+    usages[index].size = size;
+    size
+}
+
+/// Visits all entries below `dir` in the order they have been listed, parents before their
+/// children.
+fn walk(dir: &Directory, path: &str, visit: &mut impl FnMut(&str, Entry<'_>)) {
+    for (name, entry) in dir.entries() {
+        let path = format!("{path}/{name}");
+        visit(&path, entry);
+        if let Entry::Directory(child) = entry {
+            walk(child, &path, visit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FileSystem, TERMINAL_OUTPUT};
+
+    use super::{
+        directories_at_most, files_with_extension, human_size, largest_directories, write_human,
+        Usage,
+    };
+
+    fn usage(path: &str, size: u64) -> Usage {
+        Usage {
+            path: path.to_owned(),
+            size,
+        }
+    }
+
+    #[test]
+    fn queries() {
+        let fs = FileSystem::from_transcript(TERMINAL_OUTPUT).unwrap();
+
+        assert_eq!(
+            vec![usage("/", 48381165), usage("/d", 24933642)],
+            largest_directories(&fs, 2)
+        );
+        assert_eq!(4, largest_directories(&fs, 10).len());
+        assert_eq!(
+            vec![usage("/a", 94853), usage("/a/e", 584)],
+            directories_at_most(&fs, 100_000)
+        );
+        assert_eq!(
+            vec![usage("/c.dat", 8504156)],
+            files_with_extension(&fs, ".dat")
+        );
+        assert_eq!(
+            vec![usage("/a/h.lst", 62596)],
+            files_with_extension(&fs, "lst")
+        );
+        assert!(files_with_extension(&fs, "txt.gz").is_empty());
+    }
+
+    #[test]
+    fn human_readable_sizes() {
+        assert_eq!("584", human_size(584));
+        assert_eq!("1.0K", human_size(1024));
+        assert_eq!("1.1K", human_size(1025));
+        assert_eq!("93K", human_size(94853));
+        assert_eq!("8.2M", human_size(8504156));
+        assert_eq!("47M", human_size(48381165));
+        assert_eq!("1.0M", human_size((1 << 20) - 1));
+        assert_eq!("1.0G", human_size(1 << 30));
+    }
+
+    #[test]
+    fn human_output() {
+        let usages = [usage("/", 48381165), usage("/a/e", 584)];
+        let mut human = Vec::new();
+
+        write_human(&usages, &mut human).unwrap();
+
+        assert_eq!("47M\t/\n584\t/a/e\n", String::from_utf8(human).unwrap());
+    }
+}
@@ -0,0 +1,70 @@
+//! Total sizes of all directories, recorded once, so questions about them can be answered without
+//! walking the file system again.
+
+use crate::{FileSystem, Sizes};
+
+/// Total size of every directory in the file system, sorted in ascending order.
+#[derive(Debug, Clone)]
+pub struct DirectorySizes {
+    /// Sizes of all directories, including the root, in ascending order
+    sizes: Vec<u64>,
+    /// Total size of the root directory, i.e. the used disk space
+    used: u64,
+}
+
+impl DirectorySizes {
+    /// Records the sizes of all directories in a single traversal of the file system.
+    pub fn new(fs: &FileSystem) -> Self {
+        let mut sizes = fs.fold(Sizes::default()).into_sizes();
+        // The root directory is finalized last
+        let used = *sizes.last().unwrap();
+        sizes.sort_unstable();
+        Self { sizes, used }
+    }
+
+    /// Total size of the root directory.
+    pub fn used(&self) -> u64 {
+        self.used
+    }
+
+    /// Size of the smallest directory with a total size of at least `min_size`. `None` if even the
+    /// root is smaller.
+    pub fn smallest_at_least(&self, min_size: u64) -> Option<u64> {
+        let pos = self.sizes.partition_point(|&size| size < min_size);
+        self.sizes.get(pos).copied()
+    }
+
+    /// Sum of the total sizes of all directories with a total size of at most `max_size`.
+    pub fn sum_at_most(&self, max_size: u64) -> u64 {
+        let end = self.sizes.partition_point(|&size| size <= max_size);
+        self.sizes[..end].iter().sum()
+    }
+
+    /// Space which must be freed in order to have `required` unused space on a disk of size
+    /// `total`. `0` if there is already enough unused space.
+    pub fn space_to_free(&self, total: u64, required: u64) -> u64 {
+        let free = total.saturating_sub(self.used);
+        required.saturating_sub(free)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FileSystem, TERMINAL_OUTPUT};
+
+    use super::DirectorySizes;
+
+    #[test]
+    fn queries() {
+        let sizes = DirectorySizes::new(&FileSystem::from_transcript(TERMINAL_OUTPUT).unwrap());
+
+        assert_eq!(48381165, sizes.used());
+        assert_eq!(95437, sizes.sum_at_most(100_000));
+        assert_eq!(8381165, sizes.space_to_free(70_000_000, 30_000_000));
+        assert_eq!(Some(24933642), sizes.smallest_at_least(8381165));
+        assert_eq!(Some(584), sizes.smallest_at_least(584));
+        assert_eq!(Some(94853), sizes.smallest_at_least(585));
+        assert_eq!(None, sizes.smallest_at_least(48381166));
+        assert_eq!(0, sizes.space_to_free(100_000_000, 30_000_000));
+    }
+}
@@ -0,0 +1,98 @@
+//! Answers questions about the disk usage recorded in the terminal output of day 7, like `du -h`
+//! would.
+//!
+//! ```
+//! du [--json] top <N>            The N largest directories
+//! du [--json] under <SIZE>       All directories with a total size of at most SIZE bytes
+//! du [--json] ext <EXTENSION>    All files with the extension, e.g. `.dat`
+//! ```
+//!
+//! The transcript is read from `input.txt`.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, stdout, BufReader, Write},
+};
+
+use anyhow::{bail, Context, Error};
+use day_7_common::{
+    directories_at_most, files_with_extension, largest_directories, write_human, FileSystem, Usage,
+};
+use serde_json::json;
+
+fn main() -> Result<(), Error> {
+    let mut json = false;
+    let mut args = Vec::new();
+    for arg in env::args().skip(1) {
+        if arg == "--json" {
+            json = true;
+        } else {
+            args.push(arg);
+        }
+    }
+    let input = BufReader::new(File::open("input.txt").context("Can not open input file")?);
+    let fs = FileSystem::from_transcript(input)?;
+    let usages = match args.as_slice() {
+        [command, n] if command == "top" => {
+            let n = n
+                .parse()
+                .context("Number of directories must be an integer")?;
+            largest_directories(&fs, n)
+        }
+        [command, size] if command == "under" => {
+            let size = size.parse().context("Size must be an integer in bytes")?;
+            directories_at_most(&fs, size)
+        }
+        [command, extension] if command == "ext" => files_with_extension(&fs, extension),
+        _ => bail!("Usage: du [--json] (top <N> | under <SIZE> | ext <EXTENSION>)"),
+    };
+    if json {
+        write_json(&usages, stdout().lock())?;
+    } else {
+        write_human(&usages, stdout().lock())?;
+    }
+    Ok(())
+}
+
+/// JSON array of objects with `path` and `size` (in bytes).
+fn write_json(usages: &[Usage], mut out: impl Write) -> io::Result<()> {
+    let usages: Vec<_> = usages
+        .iter()
+        .map(|usage| json!({ "path": usage.path, "size": usage.size }))
+        .collect();
+    serde_json::to_writer_pretty(&mut out, &usages)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use day_7_common::Usage;
+
+    use crate::write_json;
+
+    #[test]
+    fn json_output() {
+        let usage = |path: &str, size| Usage {
+            path: path.to_owned(),
+            size,
+        };
+        let usages = [usage("/", 48381165), usage("/a/e", 584)];
+        let mut json = Vec::new();
+
+        write_json(&usages, &mut json).unwrap();
+
+        let expected = r#"[
+  {
+    "path": "/",
+    "size": 48381165
+  },
+  {
+    "path": "/a/e",
+    "size": 584
+  }
+]
+"#;
+        assert_eq!(expected, String::from_utf8(json).unwrap());
+    }
+}
@@ -38,101 +38,15 @@
 //! visible in this arrangement. Consider your map; **how many trees are visible from outside the
 //! grid?**
 
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    ops::Index,
-};
+use std::{fs::File, io::BufReader};
 
-use lines::LineStream;
+use anyhow::Error;
+use day_8_common::Grid;
 
-mod lines;
-
-fn main() {
-    let input = BufReader::new(File::open("input.txt").expect("Can not open input file"));
-    let grid = Grid::new(input);
+fn main() -> Result<(), Error> {
+    let input = BufReader::new(File::open("input.txt")?);
+    let grid = Grid::new(input)?;
     let num_visible_trees = grid.num_visible();
     println!("{num_visible_trees}");
-}
-
-struct Grid {
-    width: usize,
-    height: usize,
-    tree_heights: Vec<u8>,
-}
-
-impl Grid {
-    fn new(input: impl BufRead) -> Self {
-        let mut lines = LineStream::new(input);
-        let mut tree_heights = Vec::new();
-        let first = lines.next().expect("Input must not be empty");
-        let width = first.len();
-        let mut extend_grid =
-            |line: &[u8]| tree_heights.extend(line.iter().map(|ascii_digit| *ascii_digit - b'0'));
-        extend_grid(first);
-        while let Some(line) = lines.next() {
-            assert_eq!(width, line.len());
-            extend_grid(line)
-        }
-        let height = tree_heights.len() / width;
-        Self {
-            width,
-            height,
-            tree_heights,
-        }
-    }
-
-    fn is_visible(&self, index: usize) -> bool {
-        let row = index / self.width;
-        let col = index % self.width;
-
-        let current_height = self.tree_heights[index];
-
-        let is_shorter = |(r, c)| self[(r, c)] < current_height;
-
-        (0..row).map(|r| (r, col)).all(is_shorter)
-            || ((row + 1)..self.height).map(|r| (r, col)).all(is_shorter)
-            || (0..col).map(|c| (row, c)).all(is_shorter)
-            || ((col + 1)..self.width).map(|c| (row, c)).all(is_shorter)
-    }
-
-    fn num_visible(&self) -> usize {
-        (0..self.tree_heights.len())
-            .filter(|index| self.is_visible(*index))
-            .count()
-    }
-}
-
-// Row, Column
-impl Index<(usize, usize)> for Grid {
-    type Output = u8;
-
-    fn index(&self, index: (usize, usize)) -> &Self::Output {
-        &self.tree_heights[index.0 * self.width + index.1]
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::Grid;
-
-    const INPUT: &[u8] = "\
-        30373\n\
-        25512\n\
-        65332\n\
-        33549\n\
-        35390\n\
-    "
-    .as_bytes();
-
-    #[test]
-    fn visible_trees() {
-        let input = INPUT;
-
-        let grid = Grid::new(input);
-        let is_visible = |r: usize, c: usize| grid.is_visible(5 * r + c);
-
-        assert!(is_visible(1, 1));
-        assert!(is_visible(1, 2));
-    }
+    Ok(())
 }
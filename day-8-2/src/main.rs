@@ -88,147 +88,42 @@
 //! Consider each tree on your map. **What is the highest scenic score possible for any tree?**
 
 use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    ops::Index,
+    env,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
 };
 
-use lines::LineStream;
-
-mod lines;
-
-fn main() {
-    let input = BufReader::new(File::open("input.txt").expect("Can not open input file"));
-    let grid = Grid::new(input);
-    let num_visible_trees = grid.best_scenic_score();
-    println!("{num_visible_trees}");
-}
-
-struct Grid {
-    width: usize,
-    height: usize,
-    tree_heights: Vec<u8>,
-}
-
-impl Grid {
-    fn new(input: impl BufRead) -> Self {
-        let mut lines = LineStream::new(input);
-        let mut tree_heights = Vec::new();
-        let first = lines.next().expect("Input must not be empty");
-        let width = first.len();
-        let mut extend_grid =
-            |line: &[u8]| tree_heights.extend(line.iter().map(|ascii_digit| *ascii_digit - b'0'));
-        extend_grid(first);
-        while let Some(line) = lines.next() {
-            assert_eq!(width, line.len());
-            extend_grid(line)
-        }
-        let height = tree_heights.len() / width;
-        Self {
-            width,
-            height,
-            tree_heights,
-        }
-    }
-
-    /// (up, left, down, right)
-    fn view_range(&self, index: usize) -> (usize, usize, usize, usize) {
-        let row = index / self.width;
-        let col = index % self.width;
-
-        let current_height = self.tree_heights[index];
-
-        let fuse_blocked_view = |last_tree_height: &mut Option<u8>, coord| {
-            let this_tree_height = self[coord];
-            if let Some(lth) = *last_tree_height {
-                *last_tree_height = Some(this_tree_height);
-                (current_height > lth).then_some(())
-            } else {
-                *last_tree_height = Some(this_tree_height);
-                Some(())
-            }
-        };
-
-        let up = (0..row)
-            .rev()
-            .map(|r| (r, col))
-            .scan(None, fuse_blocked_view)
-            .count();
-        let down = ((row + 1)..self.height)
-            .map(|r| (r, col))
-            .scan(None, fuse_blocked_view)
-            .count();
-        let left = (0..col)
-            .rev()
-            .map(|c| (row, c))
-            .scan(None, fuse_blocked_view)
-            .count();
-        let right = ((col + 1)..self.width)
-            .map(|c| (row, c))
-            .scan(None, fuse_blocked_view)
-            .count();
-
-        (up, left, down, right)
-    }
-
-    fn scenic_score(&self, index: usize) -> usize {
-        let (up, left, down, right) = self.view_range(index);
-        up * left * down * right
-    }
-
-    fn best_scenic_score(&self) -> usize {
-        (0..self.tree_heights.len())
-            .map(|index| self.scenic_score(index))
-            .max()
-            .unwrap()
-    }
-}
-
-// Row, Column
-impl Index<(usize, usize)> for Grid {
-    type Output = u8;
-
-    fn index(&self, index: (usize, usize)) -> &Self::Output {
-        &self.tree_heights[index.0 * self.width + index.1]
+use anyhow::Error;
+use day_8_common::{scenic_scores_csv, scenic_scores_pgm, visibility_csv, visibility_pgm, Grid};
+
+fn main() -> Result<(), Error> {
+    let input = BufReader::new(File::open("input.txt")?);
+    let grid = Grid::new(input)?;
+    let best_scenic_score = grid.best_scenic_score();
+    println!("{best_scenic_score}");
+    let (row, col) = grid.best_tree();
+    eprintln!("Best tree is in row {row}, column {col}");
+    // Optionally export scenic scores and visibility of every tree into a directory
+    if let Some(dir) = env::args().nth(1) {
+        export(&grid, Path::new(&dir))?;
     }
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Grid;
-
-    const INPUT: &[u8] = "\
-        30373\n\
-        25512\n\
-        65332\n\
-        33549\n\
-        35390\n\
-    "
-    .as_bytes();
-
-    #[test]
-    fn view_range() {
-        let grid = Grid::new(INPUT);
-        let ranges = |r: usize, c: usize| grid.view_range(5 * r + c);
-
-        assert_eq!((1, 1, 2, 2), ranges(1, 2));
-        assert_eq!((2, 2, 1, 2), ranges(3, 2));
-    }
-
-    #[test]
-    fn scenic_scores() {
-        let grid = Grid::new(INPUT);
-        let score = |r: usize, c: usize| grid.scenic_score(5 * r + c);
-
-        assert_eq!(4, score(1, 2));
-        assert_eq!(8, score(3, 2));
-    }
-
-    #[test]
-    fn max_scenic_score() {
-        let grid = Grid::new(INPUT);
-        let best = grid.best_scenic_score();
-
-        assert_eq!(8, best);
-    }
+fn export(grid: &Grid, dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let write_file = |name: &str, write: fn(&Grid, &mut BufWriter<File>) -> io::Result<()>| {
+        let mut out = BufWriter::new(File::create(dir.join(name))?);
+        write(grid, &mut out)?;
+        out.flush()
+    };
+    write_file("scenic_scores.csv", |grid, out| {
+        scenic_scores_csv(grid, out)
+    })?;
+    write_file("scenic_scores.pgm", |grid, out| {
+        scenic_scores_pgm(grid, out)
+    })?;
+    write_file("visibility.csv", |grid, out| visibility_csv(grid, out))?;
+    write_file("visibility.pgm", |grid, out| visibility_pgm(grid, out))
 }
@@ -0,0 +1,122 @@
+//! Writes the per-tree results of the grid, so they can be inspected outside of this program. CSV
+//! files contain one line per row of trees, PGM files are grayscale images with one pixel per
+//! tree.
+
+use std::io::{self, Write};
+
+use crate::Grid;
+
+/// Scenic score of each tree as comma separated values.
+pub fn scenic_scores_csv(grid: &Grid, out: impl Write) -> io::Result<()> {
+    write_csv(grid, out, |index| grid.scenic_score(index))
+}
+
+/// `1` for each tree visible from outside the grid, `0` otherwise, as comma separated values.
+pub fn visibility_csv(grid: &Grid, out: impl Write) -> io::Result<()> {
+    write_csv(grid, out, |index| grid.is_visible(index) as usize)
+}
+
+/// Heatmap of the scenic scores as binary PGM image. Brightness is proportional to the score, the
+/// tree with the best score is white.
+pub fn scenic_scores_pgm(grid: &Grid, out: impl Write) -> io::Result<()> {
+    let best = grid.best_scenic_score().max(1);
+    write_pgm(grid, out, |index| {
+        (grid.scenic_score(index) * 255 / best) as u8
+    })
+}
+
+/// Visible trees as white, hidden ones as black pixels in a binary PGM image.
+pub fn visibility_pgm(grid: &Grid, out: impl Write) -> io::Result<()> {
+    write_pgm(
+        grid,
+        out,
+        |index| if grid.is_visible(index) { 255 } else { 0 },
+    )
+}
+
+fn write_csv(grid: &Grid, mut out: impl Write, value: impl Fn(usize) -> usize) -> io::Result<()> {
+    for row in 0..grid.height {
+        for col in 0..grid.width {
+            if col != 0 {
+                out.write_all(b",")?;
+            }
+            write!(out, "{}", value(row * grid.width + col))?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn write_pgm(grid: &Grid, mut out: impl Write, brightness: impl Fn(usize) -> u8) -> io::Result<()> {
+    write!(out, "P5\n{} {}\n255\n", grid.width, grid.height)?;
+    let pixels: Vec<u8> = (0..grid.width * grid.height).map(brightness).collect();
+    out.write_all(&pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Grid;
+
+    use super::{scenic_scores_csv, scenic_scores_pgm, visibility_csv, visibility_pgm};
+
+    const INPUT: &[u8] = "\
+        30373\n\
+        25512\n\
+        65332\n\
+        33549\n\
+        35390\n\
+    "
+    .as_bytes();
+
+    #[test]
+    fn csv() {
+        let grid = Grid::new(INPUT).unwrap();
+        let mut scores = Vec::new();
+        let mut visibility = Vec::new();
+
+        scenic_scores_csv(&grid, &mut scores).unwrap();
+        visibility_csv(&grid, &mut visibility).unwrap();
+
+        let expected_scores = "\
+            0,0,0,0,0\n\
+            0,1,4,1,0\n\
+            0,6,1,2,0\n\
+            0,1,8,3,0\n\
+            0,0,0,0,0\n\
+        ";
+        assert_eq!(expected_scores, String::from_utf8(scores).unwrap());
+        let expected_visibility = "\
+            1,1,1,1,1\n\
+            1,1,1,0,1\n\
+            1,1,0,1,1\n\
+            1,0,1,0,1\n\
+            1,1,1,1,1\n\
+        ";
+        assert_eq!(expected_visibility, String::from_utf8(visibility).unwrap());
+    }
+
+    #[test]
+    fn pgm() {
+        let grid = Grid::new(INPUT).unwrap();
+        let mut scores = Vec::new();
+        let mut visibility = Vec::new();
+
+        scenic_scores_pgm(&grid, &mut scores).unwrap();
+        visibility_pgm(&grid, &mut visibility).unwrap();
+
+        let header = b"P5\n5 5\n255\n";
+        assert_eq!(header, &scores[..header.len()]);
+        // Best tree is white, the second best (score 6 out of 8) light gray
+        assert_eq!(255, scores[header.len() + 3 * 5 + 2]);
+        assert_eq!(191, scores[header.len() + 2 * 5 + 1]);
+        assert_eq!(header.len() + 25, visibility.len());
+        assert_eq!(
+            21,
+            visibility
+                .iter()
+                .skip(header.len())
+                .filter(|&&p| p == 255)
+                .count()
+        );
+    }
+}
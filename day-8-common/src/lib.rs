@@ -0,0 +1,293 @@
+//! Tree grid of Day 8: Treetop Tree House. Part one counts the trees visible from outside the grid,
+//! part two looks for the tree with the best scenic score.
+//!
+//! Looking from every tree into all four directions would be quadratic for each row and column.
+//! Instead we sweep each row and column once per direction, keeping a monotonic stack of the trees
+//! which may still block the view of the trees to come. This yields the visibility and viewing
+//! distances of all trees in linear time.
+
+use std::{io::BufRead, ops::Index};
+
+pub use self::{
+    export::{scenic_scores_csv, scenic_scores_pgm, visibility_csv, visibility_pgm},
+    parse::InvalidGrid,
+};
+
+mod export;
+mod parse;
+
+pub struct Grid {
+    width: usize,
+    height: usize,
+    tree_heights: Vec<u8>,
+    /// Whether each tree is visible from outside the grid
+    visible: Vec<bool>,
+    /// Viewing distance of each tree (up, left, down, right)
+    view_ranges: Vec<(usize, usize, usize, usize)>,
+}
+
+impl Grid {
+    pub fn new(input: impl BufRead) -> Result<Self, InvalidGrid> {
+        let (width, tree_heights) = parse::tree_heights(input)?;
+        let height = tree_heights.len() / width;
+        let mut grid = Self {
+            width,
+            height,
+            visible: vec![false; tree_heights.len()],
+            view_ranges: vec![(0, 0, 0, 0); tree_heights.len()],
+            tree_heights,
+        };
+        grid.sweep_all();
+        Ok(grid)
+    }
+
+    /// Number of trees in each row
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of trees in each column
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_visible(&self, index: usize) -> bool {
+        self.visible[index]
+    }
+
+    pub fn num_visible(&self) -> usize {
+        self.visible.iter().filter(|&&visible| visible).count()
+    }
+
+    /// (up, left, down, right)
+    pub fn view_range(&self, index: usize) -> (usize, usize, usize, usize) {
+        self.view_ranges[index]
+    }
+
+    pub fn scenic_score(&self, index: usize) -> usize {
+        let (up, left, down, right) = self.view_range(index);
+        up * left * down * right
+    }
+
+    pub fn best_scenic_score(&self) -> usize {
+        (0..self.tree_heights.len())
+            .map(|index| self.scenic_score(index))
+            .max()
+            .unwrap()
+    }
+
+    /// (Row, Column) of the tree with the best scenic score. The first one in reading order, if
+    /// several trees share the best score. Like in [`InvalidGrid`], rows and columns start at `1`.
+    pub fn best_tree(&self) -> (usize, usize) {
+        let best_score = self.best_scenic_score();
+        let index = (0..self.tree_heights.len())
+            .find(|&index| self.scenic_score(index) == best_score)
+            .unwrap();
+        (index / self.width + 1, index % self.width + 1)
+    }
+
+    /// Fills `visible` and `view_ranges` by sweeping every row and column in both directions.
+    fn sweep_all(&mut self) {
+        let (width, height) = (self.width, self.height);
+        let mut stack = Vec::new();
+        for row in 0..height {
+            let line = move |col| row * width + col;
+            self.sweep((0..width).map(line), &mut stack, |range| &mut range.1);
+            self.sweep((0..width).rev().map(line), &mut stack, |range| &mut range.3);
+        }
+        for col in 0..width {
+            let line = move |row| row * width + col;
+            self.sweep((0..height).map(line), &mut stack, |range| &mut range.0);
+            self.sweep((0..height).rev().map(line), &mut stack, |range| {
+                &mut range.2
+            });
+        }
+    }
+
+    /// Visits the trees given by `line` in order, looking back along the line from each of them.
+    /// `direction` selects the component of the view range to fill in.
+    fn sweep(
+        &mut self,
+        line: impl Iterator<Item = usize>,
+        stack: &mut Vec<(usize, u8)>,
+        direction: impl Fn(&mut (usize, usize, usize, usize)) -> &mut usize,
+    ) {
+        // Position along the line and height of trees, which are taller than (or as tall as) any
+        // tree after them visited so far. Heights never increase from bottom to top.
+        stack.clear();
+        for (pos, index) in line.enumerate() {
+            let current_height = self.tree_heights[index];
+            while stack
+                .last()
+                .is_some_and(|&(_, height)| height < current_height)
+            {
+                stack.pop();
+            }
+            let distance = if let Some(&(blocking, _)) = stack.last() {
+                pos - blocking
+            } else {
+                // All trees between this one and the edge are shorter
+                self.visible[index] = true;
+                pos
+            };
+            *direction(&mut self.view_ranges[index]) = distance;
+            stack.push((pos, current_height));
+        }
+    }
+}
+
+// Row, Column
+impl Index<(usize, usize)> for Grid {
+    type Output = u8;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.tree_heights[index.0 * self.width + index.1]
+    }
+}
+
+/// Straightforward implementations looking from each tree into every direction. Used to verify the
+/// sweeps.
+#[cfg(test)]
+mod naive {
+    use crate::Grid;
+
+    pub fn is_visible(grid: &Grid, index: usize) -> bool {
+        let row = index / grid.width;
+        let col = index % grid.width;
+
+        let current_height = grid.tree_heights[index];
+
+        let is_shorter = |(r, c)| grid[(r, c)] < current_height;
+
+        (0..row).map(|r| (r, col)).all(is_shorter)
+            || ((row + 1)..grid.height).map(|r| (r, col)).all(is_shorter)
+            || (0..col).map(|c| (row, c)).all(is_shorter)
+            || ((col + 1)..grid.width).map(|c| (row, c)).all(is_shorter)
+    }
+
+    /// (up, left, down, right)
+    pub fn view_range(grid: &Grid, index: usize) -> (usize, usize, usize, usize) {
+        let row = index / grid.width;
+        let col = index % grid.width;
+
+        let current_height = grid.tree_heights[index];
+
+        let fuse_blocked_view = |last_tree_height: &mut Option<u8>, coord| {
+            let this_tree_height = grid[coord];
+            if let Some(lth) = *last_tree_height {
+                *last_tree_height = Some(this_tree_height);
+                (current_height > lth).then_some(())
+            } else {
+                *last_tree_height = Some(this_tree_height);
+                Some(())
+            }
+        };
+
+        let up = (0..row)
+            .rev()
+            .map(|r| (r, col))
+            .scan(None, fuse_blocked_view)
+            .count();
+        let down = ((row + 1)..grid.height)
+            .map(|r| (r, col))
+            .scan(None, fuse_blocked_view)
+            .count();
+        let left = (0..col)
+            .rev()
+            .map(|c| (row, c))
+            .scan(None, fuse_blocked_view)
+            .count();
+        let right = ((col + 1)..grid.width)
+            .map(|c| (row, c))
+            .scan(None, fuse_blocked_view)
+            .count();
+
+        (up, left, down, right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{naive, Grid};
+
+    const INPUT: &[u8] = "\
+        30373\n\
+        25512\n\
+        65332\n\
+        33549\n\
+        35390\n\
+    "
+    .as_bytes();
+
+    #[test]
+    fn visible_trees() {
+        let grid = Grid::new(INPUT).unwrap();
+        let is_visible = |r: usize, c: usize| grid.is_visible(5 * r + c);
+
+        assert!(is_visible(1, 1));
+        assert!(is_visible(1, 2));
+        assert!(!is_visible(1, 3));
+        assert_eq!(21, grid.num_visible());
+    }
+
+    #[test]
+    fn view_range() {
+        let grid = Grid::new(INPUT).unwrap();
+        let ranges = |r: usize, c: usize| grid.view_range(5 * r + c);
+
+        assert_eq!((1, 1, 2, 2), ranges(1, 2));
+        assert_eq!((2, 2, 1, 2), ranges(3, 2));
+    }
+
+    #[test]
+    fn scenic_scores() {
+        let grid = Grid::new(INPUT).unwrap();
+        let score = |r: usize, c: usize| grid.scenic_score(5 * r + c);
+
+        assert_eq!(4, score(1, 2));
+        assert_eq!(8, score(3, 2));
+    }
+
+    #[test]
+    fn max_scenic_score() {
+        let grid = Grid::new(INPUT).unwrap();
+        let best = grid.best_scenic_score();
+
+        assert_eq!(8, best);
+        // The tree in the middle of the fourth row
+        assert_eq!((4, 3), grid.best_tree());
+    }
+
+    #[test]
+    fn non_square_grid() {
+        let grid = Grid::new("1234\r\n1514\r\n1111\r\n".as_bytes()).unwrap();
+
+        assert_eq!(4, grid.width());
+        assert_eq!(3, grid.height());
+        assert_eq!(11, grid.num_visible());
+        assert_eq!(5, grid[(1, 1)]);
+    }
+
+    /// Rows of equal length consisting of ascii digits
+    fn tree_rows() -> impl Strategy<Value = Vec<Vec<u8>>> {
+        (1..12usize, 1..12usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(prop::collection::vec(b'0'..=b'9', width), height)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn sweeps_match_naive_implementation(rows in tree_rows()) {
+            let mut input = rows.join(&b'\n');
+            input.push(b'\n');
+            let grid = Grid::new(&input[..]).unwrap();
+
+            for index in 0..grid.tree_heights.len() {
+                prop_assert_eq!(naive::is_visible(&grid, index), grid.is_visible(index));
+                prop_assert_eq!(naive::view_range(&grid, index), grid.view_range(index));
+            }
+        }
+    }
+}
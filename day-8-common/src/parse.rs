@@ -0,0 +1,167 @@
+//! Parses the map of tree heights. Each line is one row of trees, each tree a single digit.
+
+use std::{error::Error, fmt, io::BufRead};
+
+use common::LineStream;
+
+/// Reasons why the input is not a valid map of trees. Rows and columns refer to lines and bytes
+/// within the input, starting at `1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidGrid {
+    /// Input does not contain a single tree.
+    Empty,
+    /// Tree height is not an ascii digit.
+    InvalidHeight { row: usize, column: usize, byte: u8 },
+    /// Row contains a different number of trees than the first one.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for InvalidGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidGrid::Empty => write!(f, "Tree grid must not be empty"),
+            InvalidGrid::InvalidHeight { row, column, byte } => write!(
+                f,
+                "Invalid tree height {:?} in row {row}, column {column}. Heights must be digits.",
+                char::from(*byte)
+            ),
+            InvalidGrid::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Row {row} contains {found} trees, but the first row contains {expected}"
+            ),
+        }
+    }
+}
+
+impl Error for InvalidGrid {}
+
+/// Width of the grid and height of all trees, row by row. Trailing whitespace (including carriage
+/// returns) is ignored, and so are empty lines before and after the grid.
+pub fn tree_heights(input: impl BufRead) -> Result<(usize, Vec<u8>), InvalidGrid> {
+    let mut lines = LineStream::new(input);
+    let mut tree_heights = Vec::new();
+    let mut width = 0;
+    let mut row = 0;
+    // First empty line since the last row of trees
+    let mut empty_line = None;
+    while let Some(line) = lines.next_line() {
+        row += 1;
+        let line = line.trim_ascii_end();
+        if line.is_empty() {
+            empty_line = empty_line.or(Some(row));
+            continue;
+        }
+        if width == 0 {
+            width = line.len();
+        } else if let Some(empty_line) = empty_line {
+            return Err(InvalidGrid::RaggedRow {
+                row: empty_line,
+                expected: width,
+                found: 0,
+            });
+        } else if line.len() != width {
+            return Err(InvalidGrid::RaggedRow {
+                row,
+                expected: width,
+                found: line.len(),
+            });
+        }
+        empty_line = None;
+        for (index, &byte) in line.iter().enumerate() {
+            if !byte.is_ascii_digit() {
+                return Err(InvalidGrid::InvalidHeight {
+                    row,
+                    column: index + 1,
+                    byte,
+                });
+            }
+            tree_heights.push(byte - b'0');
+        }
+    }
+    if tree_heights.is_empty() {
+        return Err(InvalidGrid::Empty);
+    }
+    Ok((width, tree_heights))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tree_heights, InvalidGrid};
+
+    #[test]
+    fn trailing_whitespace_and_crlf() {
+        let input = "012 \r\n345\r\n\n";
+
+        let (width, heights) = tree_heights(input.as_bytes()).unwrap();
+
+        assert_eq!(3, width);
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], heights);
+    }
+
+    #[test]
+    fn last_line_without_newline() {
+        let (width, heights) = tree_heights("12\n34".as_bytes()).unwrap();
+
+        assert_eq!(2, width);
+        assert_eq!(vec![1, 2, 3, 4], heights);
+    }
+
+    #[test]
+    fn reject_non_digits() {
+        let result = tree_heights("123\n4x6\n".as_bytes());
+
+        assert_eq!(
+            Err(InvalidGrid::InvalidHeight {
+                row: 2,
+                column: 2,
+                byte: b'x'
+            }),
+            result
+        );
+        assert_eq!(
+            "Invalid tree height 'x' in row 2, column 2. Heights must be digits.",
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn reject_ragged_rows() {
+        let result = tree_heights("123\n45\n".as_bytes());
+
+        assert_eq!(
+            Err(InvalidGrid::RaggedRow {
+                row: 2,
+                expected: 3,
+                found: 2
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn reject_empty_lines_within_grid() {
+        let result = tree_heights("12\n\n34\n".as_bytes());
+
+        assert_eq!(
+            Err(InvalidGrid::RaggedRow {
+                row: 2,
+                expected: 2,
+                found: 0
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn reject_empty_input() {
+        assert_eq!(Err(InvalidGrid::Empty), tree_heights(" \n".as_bytes()));
+    }
+}
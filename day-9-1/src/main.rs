@@ -264,125 +264,27 @@
 //! Simulate your complete hypothetical series of motions. How many positions does the tail of the
 //! rope visit at least once?
 
-use atoi::FromRadix10Checked;
 use std::{
-    collections::HashSet,
     fs::File,
     io::{BufRead, BufReader},
 };
 
-mod lines;
+use anyhow::Error;
+use day_9_common::{ParseError, Steps, World};
 
-fn main() {
-    let input = BufReader::new(File::open("input.txt").expect("Can not open input file"));
-    let n = num_visited_by_tail(input);
+fn main() -> Result<(), Error> {
+    let input = BufReader::new(File::open("input.txt")?);
+    let n = num_visited_by_tail(input)?;
     println!("{n}");
+    Ok(())
 }
 
-fn num_visited_by_tail(input: impl BufRead) -> usize {
-    let mut lines = lines::LineStream::new(input);
-    let mut world = World::new();
-    while let Some(line) = lines.next() {
-        let inst = Instruction::from_line(line);
-        world.update(inst);
-    }
-    world.num_visited_by_tail()
-}
-
-struct World {
-    head: (i32, i32),
-    tail: (i32, i32),
-    visited: HashSet<(i32, i32)>,
-}
-
-impl World {
-    fn new() -> Self {
-        let mut visited = HashSet::new();
-        visited.insert((0, 0));
-        Self {
-            head: (0, 0),
-            tail: (0, 0),
-            visited,
-        }
-    }
-
-    fn update(&mut self, inst: Instruction) {
-        for (dx, dy) in inst {
-            // update head
-            self.head.0 += dx;
-            self.head.1 += dy;
-            // update tail
-            let dtx = self.head.0 - self.tail.0;
-            let dty = self.head.1 - self.tail.1;
-            if dtx.abs() > 1 || dty.abs() > 1 {
-                // Tail is no longer adjacent to head
-                self.tail.0 += dtx.signum();
-                self.tail.1 += dty.signum();
-            }
-            self.visited.insert(self.tail);
-        }
-    }
-
-    fn num_visited_by_tail(&self) -> usize {
-        self.visited.len()
-    }
-}
-
-#[derive(Debug)]
-struct Instruction {
-    steps: i32,
-    dir: Direction,
-}
-
-impl Instruction {
-    fn from_line(line: &[u8]) -> Self {
-        let (steps, _) = i32::from_radix_10_checked(&line[2..]);
-        let steps = steps.unwrap();
-        let dir = Direction::from_byte(line[0]);
-        Self { steps, dir }
-    }
-}
-
-impl Iterator for Instruction {
-    type Item = (i32, i32);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.steps == 0 {
-            None
-        } else {
-            self.steps -= 1;
-            Some(self.dir.delta())
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn from_byte(byte: u8) -> Self {
-        match byte {
-            b'U' => Self::Up,
-            b'D' => Self::Down,
-            b'R' => Self::Right,
-            b'L' => Self::Left,
-            _ => panic!("yagni"),
-        }
-    }
-
-    fn delta(self) -> (i32, i32) {
-        match self {
-            Direction::Up => (0, 1),
-            Direction::Down => (0, -1),
-            Direction::Left => (-1, 0),
-            Direction::Right => (1, 0),
-        }
+fn num_visited_by_tail(input: impl BufRead) -> Result<usize, ParseError> {
+    let mut world = World::new(2);
+    for step in Steps::new(input) {
+        world.step(step?);
     }
+    Ok(world.num_visited_by_tail())
 }
 
 #[cfg(test)]
@@ -403,6 +305,6 @@ mod tests {
 
     #[test]
     fn number_of_fields_visited() {
-        assert_eq!(13, num_visited_by_tail(INPUT));
+        assert_eq!(13, num_visited_by_tail(INPUT).unwrap());
     }
 }
@@ -709,139 +709,75 @@
 //! Simulate your complete series of motions on a larger rope with ten knots. **How many positions
 //! does the tail of the rope visit at least once?**
 
-use atoi::FromRadix10Checked;
 use std::{
-    collections::HashSet,
     fs::File,
-    io::{BufRead, BufReader},
+    io::{stderr, BufRead, BufReader, Write},
 };
 
-mod lines;
-
-fn main() {
-    let input = BufReader::new(File::open("input.txt").expect("Can not open input file"));
-    let n = num_visited_by_tail(input);
-    println!("{n}");
-}
-
-fn num_visited_by_tail(input: impl BufRead) -> usize {
-    let mut lines = lines::LineStream::new(input);
-    let mut world = World::new(9);
-    while let Some(line) = lines.next() {
-        let inst = Instruction::from_line(line);
-        world.update(inst);
-    }
-    world.num_visited_by_tail()
-}
-
-struct World {
-    head: (i32, i32),
-    knots: Vec<(i32, i32)>,
-    visited: HashSet<(i32, i32)>,
-}
-
-impl World {
-    fn new(num_knots: usize) -> Self {
-        let mut visited = HashSet::new();
-        visited.insert((0, 0));
-        Self {
-            head: (0, 0),
-            knots: vec![(0, 0); num_knots],
-            visited,
-        }
-    }
+use anyhow::{bail, Context, Error};
+use day_9_common::{
+    draw_visited, trace, Frame, Granularity, Instructions, ParseError, Steps, Visited, World,
+};
 
-    fn update(&mut self, inst: Instruction) {
-        for (dx, dy) in inst {
-            // update head
-            self.head.0 += dx;
-            self.head.1 += dy;
-            // update tail
-            let mut pred = self.head;
-            for knot in &mut self.knots {
-                Self::update_knot(knot, pred);
-                pred = *knot;
+fn main() -> Result<(), Error> {
+    // Number of knots, including the head. Defaults to the ten knots of the puzzle.
+    let mut num_knots = 10;
+    let mut granularity = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--trace" => granularity = Some(Granularity::Instruction),
+            "--trace-steps" => granularity = Some(Granularity::Step),
+            _ => {
+                num_knots = arg
+                    .parse()
+                    .context("Number of knots must be a positive integer")?
             }
-            self.visited
-                .insert(*self.knots.last().unwrap_or(&self.head));
         }
     }
-
-    fn update_knot(knot: &mut (i32, i32), pred: (i32, i32)) {
-        let dtx = pred.0 - knot.0;
-        let dty = pred.1 - knot.1;
-        if dtx.abs() > 1 || dty.abs() > 1 {
-            // Tail is no longer adjacent to head
-            knot.0 += dtx.signum();
-            knot.1 += dty.signum();
-        }
-    }
-
-    fn num_visited_by_tail(&self) -> usize {
-        self.visited.len()
-    }
-}
-
-#[derive(Debug)]
-struct Instruction {
-    steps: i32,
-    dir: Direction,
-}
-
-impl Instruction {
-    fn from_line(line: &[u8]) -> Self {
-        let (steps, _) = i32::from_radix_10_checked(&line[2..]);
-        let steps = steps.unwrap();
-        let dir = Direction::from_byte(line[0]);
-        Self { steps, dir }
+    if num_knots == 0 {
+        bail!("Number of knots must be a positive integer");
     }
+    let input = BufReader::new(File::open("input.txt")?);
+    let n = if let Some(granularity) = granularity {
+        trace_visited_by_tail(input, num_knots, granularity, stderr().lock())?
+    } else {
+        num_visited_by_tail(input, num_knots)?
+    };
+    println!("{n}");
+    Ok(())
 }
 
-impl Iterator for Instruction {
-    type Item = (i32, i32);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.steps == 0 {
-            None
-        } else {
-            self.steps -= 1;
-            Some(self.dir.delta())
-        }
+fn num_visited_by_tail(input: impl BufRead, num_knots: usize) -> Result<usize, ParseError> {
+    let mut world = World::new(num_knots);
+    for step in Steps::new(input) {
+        world.step(step?);
     }
+    Ok(world.num_visited_by_tail())
 }
 
-#[derive(Clone, Copy, Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn from_byte(byte: u8) -> Self {
-        match byte {
-            b'U' => Self::Up,
-            b'D' => Self::Down,
-            b'R' => Self::Right,
-            b'L' => Self::Left,
-            _ => panic!("yagni"),
-        }
-    }
-
-    fn delta(self) -> (i32, i32) {
-        match self {
-            Direction::Up => (0, 1),
-            Direction::Down => (0, -1),
-            Direction::Left => (-1, 0),
-            Direction::Right => (1, 0),
-        }
-    }
+/// Like [`num_visited_by_tail`], but draws the rope along the way and the positions visited by the
+/// tail at the end. The whole input is parsed before drawing, so nothing is drawn for invalid input.
+fn trace_visited_by_tail(
+    input: impl BufRead,
+    num_knots: usize,
+    granularity: Granularity,
+    mut out: impl Write,
+) -> Result<usize, Error> {
+    let instructions = Instructions::new(input).collect::<Result<Vec<_>, _>>()?;
+    let mut world = World::new(num_knots);
+    trace(&mut world, instructions, granularity, None, &mut out)?;
+    let tail = num_knots - 1;
+    let frame = Frame::bounding(world.visited(tail).positions());
+    writeln!(out, "== Visited by tail ==\n")?;
+    draw_visited(&world, tail, &frame, &mut out)?;
+    Ok(world.num_visited_by_tail())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::num_visited_by_tail;
+    use day_9_common::{Granularity, InvalidInstruction, ParseError};
+
+    use crate::{num_visited_by_tail, trace_visited_by_tail};
 
     const INPUT: &[u8] = "\
         R 4\n\
@@ -857,6 +793,25 @@ mod tests {
 
     #[test]
     fn number_of_fields_visited() {
-        assert_eq!(1, num_visited_by_tail(INPUT));
+        assert_eq!(1, num_visited_by_tail(INPUT, 10).unwrap());
+    }
+
+    #[test]
+    fn trace_reports_invalid_instruction() {
+        let input = "R 4\nU 4\nX 3\n".as_bytes();
+        let mut out = Vec::new();
+
+        let error = trace_visited_by_tail(input, 10, Granularity::Instruction, &mut out)
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+
+        let expected = ParseError {
+            line: 3,
+            reason: InvalidInstruction::UnknownDirection,
+        };
+        assert_eq!(expected, error);
+        assert_eq!(expected, num_visited_by_tail(input, 10).unwrap_err());
+        assert!(out.is_empty());
     }
 }
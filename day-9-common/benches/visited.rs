@@ -0,0 +1,56 @@
+//! Compares the different ways of tracking visited positions on a long list of random moves.
+
+use std::collections::HashSet;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day_9_common::{Direction, Instruction, TileMap, Visited, World};
+
+/// Random instructions. Uses a simple xorshift generator, so every run sees the same moves.
+fn random_instructions(num: usize) -> Vec<(Direction, i32)> {
+    let directions = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..num)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let dir = directions[(state % 4) as usize];
+            let steps = (state >> 8) % 20 + 1;
+            (dir, steps as i32)
+        })
+        .collect()
+}
+
+fn simulate<V: Visited>(moves: &[(Direction, i32)]) -> usize {
+    let mut world = World::<V>::with_visited(10);
+    for &(dir, steps) in moves {
+        world.update(Instruction::new(dir, steps));
+    }
+    world.num_visited_by_tail()
+}
+
+fn visited(c: &mut Criterion) {
+    let mut group = c.benchmark_group("visited");
+    for num_moves in [2_000, 100_000] {
+        let moves = random_instructions(num_moves);
+        group.bench_with_input(
+            BenchmarkId::new("HashSet", num_moves),
+            &moves,
+            |b, moves| b.iter(|| simulate::<HashSet<(i32, i32)>>(moves)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("TileMap", num_moves),
+            &moves,
+            |b, moves| b.iter(|| simulate::<TileMap>(moves)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, visited);
+criterion_main!(benches);
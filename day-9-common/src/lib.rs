@@ -0,0 +1,297 @@
+//! Rope simulation for Day 9: Rope Bridge. Part one simulates a rope with two knots, part two one
+//! with ten knots. Here the number of knots is just a parameter of the [`World`].
+
+use atoi::FromRadix10Checked;
+use std::fmt;
+
+pub use self::{
+    render::{draw_knots, draw_visited, trace, Frame, Granularity},
+    steps::{Instructions, InvalidInstruction, ParseError, Steps},
+    visited::{TileMap, Visited},
+};
+
+mod render;
+mod steps;
+mod visited;
+
+pub struct World<V = TileMap> {
+    /// Position of each knot. The head is the first knot, the tail the last one.
+    knots: Vec<(i32, i32)>,
+    /// Positions visited by each knot, in the same order as `knots`.
+    visited: Vec<V>,
+}
+
+impl World {
+    /// A rope with `num_knots` knots, including the head, all starting at the origin.
+    pub fn new(num_knots: usize) -> Self {
+        Self::with_visited(num_knots)
+    }
+}
+
+impl<V> World<V> {
+    /// Current positions of all knots, starting with the head.
+    pub fn knots(&self) -> &[(i32, i32)] {
+        &self.knots
+    }
+}
+
+impl<V> World<V>
+where
+    V: Visited,
+{
+    /// Like [`World::new`], but allows to choose how visited positions are tracked.
+    pub fn with_visited(num_knots: usize) -> Self {
+        assert!(num_knots > 0, "A rope must have at least a head");
+        let visited = (0..num_knots)
+            .map(|_| {
+                let mut visited = V::default();
+                visited.insert((0, 0));
+                visited
+            })
+            .collect();
+        Self {
+            knots: vec![(0, 0); num_knots],
+            visited,
+        }
+    }
+
+    pub fn update(&mut self, inst: Instruction) {
+        for delta in inst {
+            self.step(delta);
+        }
+    }
+
+    /// Moves the head by a single step and lets all other knots follow it.
+    pub fn step(&mut self, (dx, dy): (i32, i32)) {
+        // update head
+        self.knots[0].0 += dx;
+        self.knots[0].1 += dy;
+        self.visited[0].insert(self.knots[0]);
+        // update tail
+        for index in 1..self.knots.len() {
+            let pred = self.knots[index - 1];
+            let knot = &mut self.knots[index];
+            update_knot(knot, pred);
+            self.visited[index].insert(*knot);
+        }
+    }
+
+    /// Positions visited at least once by the knot with the given index. The head has index `0`.
+    pub fn visited(&self, knot: usize) -> &V {
+        &self.visited[knot]
+    }
+
+    pub fn num_visited_by_tail(&self) -> usize {
+        self.visited.last().unwrap().len()
+    }
+}
+
+fn update_knot(knot: &mut (i32, i32), pred: (i32, i32)) {
+    let dtx = pred.0 - knot.0;
+    let dty = pred.1 - knot.1;
+    if dtx.abs() > 1 || dty.abs() > 1 {
+        // Knot is no longer adjacent to its predecessor
+        knot.0 += dtx.signum();
+        knot.1 += dty.signum();
+    }
+}
+
+#[derive(Debug)]
+pub struct Instruction {
+    steps: i32,
+    dir: Direction,
+}
+
+impl Instruction {
+    pub fn new(dir: Direction, steps: i32) -> Self {
+        Self { steps, dir }
+    }
+
+    /// Like [`Instruction::parse`], but panics if the line is not a valid instruction.
+    pub fn from_line(line: &[u8]) -> Self {
+        Self::parse(line).unwrap()
+    }
+
+    /// Parses lines like `R 4`. Direction and number of steps are separated by a single space.
+    pub fn parse(line: &[u8]) -> Result<Self, InvalidInstruction> {
+        let separator = line
+            .iter()
+            .position(|&byte| byte == b' ')
+            .ok_or(InvalidInstruction::MissingSeparator)?;
+        let dir = Direction::from_bytes(&line[..separator])
+            .ok_or(InvalidInstruction::UnknownDirection)?;
+        let digits = &line[separator + 1..];
+        let steps = match i32::from_radix_10_checked(digits) {
+            (Some(steps), used) if used != 0 && used == digits.len() => steps,
+            _ => return Err(InvalidInstruction::InvalidSteps),
+        };
+        Ok(Self { steps, dir })
+    }
+}
+
+impl Iterator for Instruction {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.steps == 0 {
+            None
+        } else {
+            self.steps -= 1;
+            Some(self.dir.delta())
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.dir, self.steps)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let dir = match bytes {
+            b"U" => Self::Up,
+            b"D" => Self::Down,
+            b"R" => Self::Right,
+            b"L" => Self::Left,
+            b"UL" => Self::UpLeft,
+            b"UR" => Self::UpRight,
+            b"DL" => Self::DownLeft,
+            b"DR" => Self::DownRight,
+            _ => return None,
+        };
+        Some(dir)
+    }
+
+    pub fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, 1),
+            Direction::Down => (0, -1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, 1),
+            Direction::UpRight => (1, 1),
+            Direction::DownLeft => (-1, -1),
+            Direction::DownRight => (1, -1),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Direction::Up => "U",
+            Direction::Down => "D",
+            Direction::Left => "L",
+            Direction::Right => "R",
+            Direction::UpLeft => "UL",
+            Direction::UpRight => "UR",
+            Direction::DownLeft => "DL",
+            Direction::DownRight => "DR",
+        };
+        f.write_str(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{Instruction, Visited, World};
+
+    fn simulate(num_knots: usize, input: &str) -> World {
+        let mut world = World::new(num_knots);
+        for line in input.lines() {
+            world.update(Instruction::from_line(line.as_bytes()));
+        }
+        world
+    }
+
+    const SMALL_EXAMPLE: &str = "\
+        R 4\n\
+        U 4\n\
+        L 3\n\
+        D 1\n\
+        R 4\n\
+        D 1\n\
+        L 5\n\
+        R 2\n\
+    ";
+
+    #[test]
+    fn two_knots() {
+        let world = simulate(2, SMALL_EXAMPLE);
+
+        assert_eq!(13, world.num_visited_by_tail());
+        assert_eq!(&[(2, 2), (1, 2)], world.knots());
+    }
+
+    #[test]
+    fn ten_knots() {
+        let world = simulate(10, SMALL_EXAMPLE);
+
+        assert_eq!(1, world.num_visited_by_tail());
+    }
+
+    #[test]
+    fn visited_by_every_knot() {
+        let input = "\
+            R 5\n\
+            U 8\n\
+            L 8\n\
+            D 3\n\
+            R 17\n\
+            D 10\n\
+            L 25\n\
+            U 20\n\
+        ";
+
+        let world = simulate(10, input);
+
+        assert_eq!(36, world.num_visited_by_tail());
+        assert_eq!(36, world.visited(9).len());
+        // Every knot visits at least as many positions as the one following it
+        for knot in 1..10 {
+            assert!(world.visited(knot - 1).len() >= world.visited(knot).len());
+        }
+    }
+
+    #[test]
+    fn hash_set_and_tile_map_agree() {
+        let mut hash_set = World::<HashSet<(i32, i32)>>::with_visited(10);
+        let mut tile_map = World::new(10);
+        for line in SMALL_EXAMPLE.lines().chain(["L 200", "D 130", "R 70"]) {
+            hash_set.update(Instruction::from_line(line.as_bytes()));
+            tile_map.update(Instruction::from_line(line.as_bytes()));
+        }
+
+        for knot in 0..10 {
+            assert_eq!(hash_set.visited(knot).len(), tile_map.visited(knot).len());
+            assert!(tile_map
+                .visited(knot)
+                .positions()
+                .all(|pos| hash_set.visited(knot).contains(&pos)));
+        }
+    }
+
+    #[test]
+    fn diagonal_moves() {
+        let world = simulate(3, "UR 3\nDL 1\n");
+
+        assert_eq!(&[(2, 2), (2, 2), (1, 1)], world.knots());
+        assert_eq!(2, world.num_visited_by_tail());
+    }
+}
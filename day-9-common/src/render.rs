@@ -0,0 +1,308 @@
+//! Draws the rope the same way the puzzle description does, so the simulation can be compared
+//! against the examples.
+
+use std::io::{self, Write};
+
+use crate::{Instruction, Visited, World};
+
+/// Rectangular section of the world which is drawn. Both corners are inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// Lower left corner
+    pub min: (i32, i32),
+    /// Upper right corner
+    pub max: (i32, i32),
+}
+
+impl Frame {
+    pub fn new(min: (i32, i32), max: (i32, i32)) -> Self {
+        Self { min, max }
+    }
+
+    /// Smallest frame containing the starting position and all of the positions.
+    pub fn bounding(positions: impl IntoIterator<Item = (i32, i32)>) -> Self {
+        let mut frame = Self::new((0, 0), (0, 0));
+        for pos in positions {
+            frame.include(pos);
+        }
+        frame
+    }
+
+    /// Grows the frame, so it contains `pos`.
+    pub fn include(&mut self, pos: (i32, i32)) {
+        self.min = (self.min.0.min(pos.0), self.min.1.min(pos.1));
+        self.max = (self.max.0.max(pos.0), self.max.1.max(pos.1));
+    }
+
+    /// Rows from top to bottom
+    fn rows(&self) -> impl Iterator<Item = i32> {
+        (self.min.1..=self.max.1).rev()
+    }
+
+    /// Columns from left to right
+    fn columns(&self) -> impl Iterator<Item = i32> {
+        self.min.0..=self.max.0
+    }
+}
+
+/// How often [`trace`] draws the rope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Granularity {
+    /// After every single step of the head
+    Step,
+    /// After every instruction
+    Instruction,
+}
+
+/// Draws the knots of the rope within the frame. The head is drawn as `H`, the other knots by their
+/// index. In a rope with only two knots the tail is drawn as `T`. `s` marks the starting position.
+/// Knots closer to the head cover the ones following them. Rows in which one knot covers others
+/// are annotated with a note, like the examples in the puzzle.
+pub fn draw_knots<V>(world: &World<V>, frame: &Frame, mut out: impl Write) -> io::Result<()> {
+    let knots = world.knots();
+    let mut labels = Vec::new();
+    let mut notes = Vec::new();
+    for y in frame.rows() {
+        notes.clear();
+        for x in frame.columns() {
+            labels.clear();
+            labels.extend(
+                knots
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &knot)| knot == (x, y))
+                    .map(|(index, _)| knot_label(index, knots.len())),
+            );
+            if (x, y) == (0, 0) {
+                labels.push('s');
+            }
+            out.write_all(&[labels.first().map_or(b'.', |&label| label as u8)])?;
+            if labels.len() > 1 {
+                let covered: Vec<_> = labels[1..].iter().map(char::to_string).collect();
+                notes.push(format!("{} covers {}", labels[0], covered.join(", ")));
+            }
+        }
+        if !notes.is_empty() {
+            write!(out, "  ({})", notes.join("; "))?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Draws the positions visited by the knot with the given index as `#`. `s` marks the starting
+/// position.
+pub fn draw_visited<V>(
+    world: &World<V>,
+    knot: usize,
+    frame: &Frame,
+    mut out: impl Write,
+) -> io::Result<()>
+where
+    V: Visited,
+{
+    let visited = world.visited(knot);
+    for y in frame.rows() {
+        for x in frame.columns() {
+            let cell = if (x, y) == (0, 0) {
+                b's'
+            } else if visited.contains((x, y)) {
+                b'#'
+            } else {
+                b'.'
+            };
+            out.write_all(&[cell])?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Applies the instructions to the world and draws the rope along the way, using the same format
+/// as the examples in the puzzle. If no frame is given, it grows automatically with the area
+/// covered by the rope so far.
+pub fn trace<V>(
+    world: &mut World<V>,
+    instructions: impl IntoIterator<Item = Instruction>,
+    granularity: Granularity,
+    frame: Option<Frame>,
+    mut out: impl Write,
+) -> io::Result<()>
+where
+    V: Visited,
+{
+    let fixed = frame.is_some();
+    let mut frame = frame.unwrap_or_else(|| Frame::bounding(world.knots().iter().copied()));
+    let mut draw = |world: &World<V>, out: &mut dyn Write| {
+        if !fixed {
+            for &knot in world.knots() {
+                frame.include(knot);
+            }
+        }
+        draw_knots(world, &frame, &mut *out)?;
+        writeln!(out)
+    };
+
+    writeln!(out, "== Initial State ==\n")?;
+    draw(world, &mut out)?;
+    for inst in instructions {
+        writeln!(out, "== {inst} ==\n")?;
+        for delta in inst {
+            world.step(delta);
+            if granularity == Granularity::Step {
+                draw(world, &mut out)?;
+            }
+        }
+        if granularity == Granularity::Instruction {
+            draw(world, &mut out)?;
+        }
+    }
+    Ok(())
+}
+
+fn knot_label(index: usize, num_knots: usize) -> char {
+    match index {
+        0 => 'H',
+        1 if num_knots == 2 => 'T',
+        1..=9 => char::from_digit(index as u32, 10).unwrap(),
+        // Out of digits
+        _ => '*',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Instruction, Visited, World};
+
+    use super::{draw_visited, trace, Frame, Granularity};
+
+    #[test]
+    fn trace_steps_of_short_rope() {
+        let instructions = ["R 4", "U 2"]
+            .iter()
+            .map(|line| Instruction::from_line(line.as_bytes()));
+        let mut world = World::new(2);
+        let mut out = Vec::new();
+
+        trace(
+            &mut world,
+            instructions,
+            Granularity::Step,
+            Some(Frame::new((0, 0), (5, 4))),
+            &mut out,
+        )
+        .unwrap();
+
+        let expected = "\
+            == Initial State ==\n\
+            \n\
+            ......\n\
+            ......\n\
+            ......\n\
+            ......\n\
+            H.....  (H covers T, s)\n\
+            \n\
+            == R 4 ==\n\
+            \n\
+            ......\n\
+            ......\n\
+            ......\n\
+            ......\n\
+            TH....  (T covers s)\n\
+            \n\
+            ......\n\
+            ......\n\
+            ......\n\
+            ......\n\
+            sTH...\n\
+            \n\
+            ......\n\
+            ......\n\
+            ......\n\
+            ......\n\
+            s.TH..\n\
+            \n\
+            ......\n\
+            ......\n\
+            ......\n\
+            ......\n\
+            s..TH.\n\
+            \n\
+            == U 2 ==\n\
+            \n\
+            ......\n\
+            ......\n\
+            ......\n\
+            ....H.\n\
+            s..T..\n\
+            \n\
+            ......\n\
+            ......\n\
+            ....H.\n\
+            ....T.\n\
+            s.....\n\
+            \n\
+        ";
+        assert_eq!(expected, String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn trace_instructions_of_long_rope() {
+        let instructions = ["R 4", "U 4"]
+            .iter()
+            .map(|line| Instruction::from_line(line.as_bytes()));
+        let mut world = World::new(10);
+        let mut out = Vec::new();
+
+        trace(
+            &mut world,
+            instructions,
+            Granularity::Instruction,
+            None,
+            &mut out,
+        )
+        .unwrap();
+
+        let expected = "\
+            == Initial State ==\n\
+            \n\
+            H  (H covers 1, 2, 3, 4, 5, 6, 7, 8, 9, s)\n\
+            \n\
+            == R 4 ==\n\
+            \n\
+            4321H  (4 covers 5, 6, 7, 8, 9, s)\n\
+            \n\
+            == U 4 ==\n\
+            \n\
+            ....H\n\
+            ....1\n\
+            ..432\n\
+            .5...\n\
+            6....  (6 covers 7, 8, 9, s)\n\
+            \n\
+        ";
+        assert_eq!(expected, String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn visited_by_tail() {
+        let input = ["R 4", "U 4", "L 3", "D 1", "R 4", "D 1", "L 5", "R 2"];
+        let mut world = World::new(2);
+        for line in input {
+            world.update(Instruction::from_line(line.as_bytes()));
+        }
+        let frame = Frame::bounding(world.visited(1).positions());
+        let mut out = Vec::new();
+
+        draw_visited(&world, 1, &frame, &mut out).unwrap();
+
+        let expected = "\
+            ..##.\n\
+            ...##\n\
+            .####\n\
+            ....#\n\
+            s###.\n\
+        ";
+        assert_eq!(expected, String::from_utf8(out).unwrap());
+    }
+}
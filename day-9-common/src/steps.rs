@@ -0,0 +1,183 @@
+//! Streams the instructions, or the single steps of the head, out of the puzzle input, without
+//! holding more than one line in memory.
+
+use std::{error::Error, fmt, io::BufRead};
+
+use common::InputIterator;
+
+use crate::Instruction;
+
+/// Reason why a line could not be parsed into an [`Instruction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidInstruction {
+    /// Direction and number of steps are not separated by a space.
+    MissingSeparator,
+    /// Direction is none of `U`, `D`, `L`, `R`, `UL`, `UR`, `DL` or `DR`.
+    UnknownDirection,
+    /// Number of steps is not a non-negative integer.
+    InvalidSteps,
+}
+
+impl fmt::Display for InvalidInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            InvalidInstruction::MissingSeparator => {
+                "direction and steps must be separated by a space"
+            }
+            InvalidInstruction::UnknownDirection => "unknown direction",
+            InvalidInstruction::InvalidSteps => "number of steps must be a non-negative integer",
+        };
+        f.write_str(reason)
+    }
+}
+
+impl Error for InvalidInstruction {}
+
+/// An invalid instruction, together with the line it originated from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line number, starting with `1`.
+    pub line: usize,
+    pub reason: InvalidInstruction,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid instruction in line {}: {}",
+            self.line, self.reason
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+type ParseLine = fn(&[u8]) -> Result<Instruction, InvalidInstruction>;
+
+/// Iterates over the instructions in the input, one per line, along with the line of each invalid
+/// one.
+pub struct Instructions<R> {
+    lines: InputIterator<R, ParseLine>,
+    /// Number of the line the last instruction originated from.
+    line: usize,
+}
+
+impl<R> Instructions<R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            lines: InputIterator::new(input, Instruction::parse as ParseLine),
+            line: 0,
+        }
+    }
+}
+
+impl<R> Iterator for Instructions<R>
+where
+    R: BufRead,
+{
+    type Item = Result<Instruction, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let inst = self.lines.next()?;
+        self.line += 1;
+        let line = self.line;
+        Some(inst.map_err(|reason| ParseError { line, reason }))
+    }
+}
+
+/// Iterates over the unit moves of the head for all instructions in the input. E.g. `R 2` followed
+/// by `U 1` yields `(1, 0)`, `(1, 0)` and `(0, 1)`.
+pub struct Steps<R> {
+    instructions: Instructions<R>,
+    current: Option<Instruction>,
+}
+
+impl<R> Steps<R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            instructions: Instructions::new(input),
+            current: None,
+        }
+    }
+}
+
+impl<R> Iterator for Steps<R>
+where
+    R: BufRead,
+{
+    type Item = Result<(i32, i32), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(step) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(Ok(step));
+            }
+            match self.instructions.next()? {
+                Ok(inst) => self.current = Some(inst),
+                Err(error) => {
+                    self.current = None;
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Instructions, InvalidInstruction, ParseError, Steps};
+
+    #[test]
+    fn flatten_instructions_into_steps() {
+        let input = "R 2\nU 0\nDL 1\n";
+
+        let steps: Result<Vec<_>, _> = Steps::new(input.as_bytes()).collect();
+
+        assert_eq!(vec![(1, 0), (1, 0), (-1, -1)], steps.unwrap());
+    }
+
+    #[test]
+    fn report_line_of_invalid_instruction() {
+        let input = "R 1\nL 1\nX 3\nU1\nD x\n";
+
+        let steps: Vec<_> = Steps::new(input.as_bytes()).collect();
+
+        let error = |line, reason| Err(ParseError { line, reason });
+        assert_eq!(
+            vec![
+                Ok((1, 0)),
+                Ok((-1, 0)),
+                error(3, InvalidInstruction::UnknownDirection),
+                error(4, InvalidInstruction::MissingSeparator),
+                error(5, InvalidInstruction::InvalidSteps),
+            ],
+            steps
+        );
+        assert_eq!(
+            "Invalid instruction in line 3: unknown direction",
+            steps[2].as_ref().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn report_line_of_invalid_instruction_without_steps() {
+        let input = "R 1\nX 3\nL 0\n";
+
+        let lines: Vec<_> = Instructions::new(input.as_bytes())
+            .map(|inst| inst.map(|inst| inst.to_string()))
+            .collect();
+
+        assert_eq!(
+            vec![
+                Ok("R 1".to_owned()),
+                Err(ParseError {
+                    line: 2,
+                    reason: InvalidInstruction::UnknownDirection
+                }),
+                Ok("L 0".to_owned()),
+            ],
+            lines
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Sets of positions visited by a knot. Every single step of the rope inserts a position for each
+//! knot, so this is the hot path of the simulation.
+
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+/// Set of positions visited by a knot.
+pub trait Visited: Default {
+    /// Marks the position as visited.
+    fn insert(&mut self, pos: (i32, i32));
+
+    fn contains(&self, pos: (i32, i32)) -> bool;
+
+    /// Number of distinct positions visited.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// All visited positions in no particular order.
+    fn positions(&self) -> impl Iterator<Item = (i32, i32)> + '_;
+}
+
+impl Visited for HashSet<(i32, i32)> {
+    fn insert(&mut self, pos: (i32, i32)) {
+        HashSet::insert(self, pos);
+    }
+
+    fn contains(&self, pos: (i32, i32)) -> bool {
+        HashSet::contains(self, &pos)
+    }
+
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+
+    fn positions(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.iter().copied()
+    }
+}
+
+/// Number of positions along each side of a tile.
+const TILE_SIZE: i32 = 64;
+
+/// One bit per position. Each row of the tile is one `u64`.
+type Tile = [u64; TILE_SIZE as usize];
+
+/// Bitmap of visited positions, which grows dynamically in square tiles. Consecutive steps of the
+/// rope almost always stay within the same tile, so we remember the last tile touched and only
+/// have to look up a new one if the knot crosses a tile boundary.
+#[derive(Clone, Default)]
+pub struct TileMap {
+    /// Index into `tiles` for the coordinates of each tile.
+    index: HashMap<(i32, i32), usize>,
+    tiles: Vec<Tile>,
+    /// Coordinates and index of the tile touched by the last insert.
+    last: Option<((i32, i32), usize)>,
+    len: usize,
+}
+
+impl TileMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn tile_index(&mut self, tile: (i32, i32)) -> usize {
+        match self.last {
+            Some((last_tile, last_index)) if last_tile == tile => last_index,
+            _ => {
+                let index = match self.index.entry(tile) {
+                    Entry::Occupied(entry) => *entry.get(),
+                    Entry::Vacant(entry) => {
+                        self.tiles.push([0; TILE_SIZE as usize]);
+                        *entry.insert(self.tiles.len() - 1)
+                    }
+                };
+                self.last = Some((tile, index));
+                index
+            }
+        }
+    }
+}
+
+impl Visited for TileMap {
+    fn insert(&mut self, pos: (i32, i32)) {
+        let (tile, (x, y)) = split(pos);
+        let index = self.tile_index(tile);
+        let row = &mut self.tiles[index][y];
+        let bit = 1 << x;
+        if *row & bit == 0 {
+            *row |= bit;
+            self.len += 1;
+        }
+    }
+
+    fn contains(&self, pos: (i32, i32)) -> bool {
+        let (tile, (x, y)) = split(pos);
+        self.index
+            .get(&tile)
+            .is_some_and(|&index| self.tiles[index][y] & (1 << x) != 0)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn positions(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.index
+            .iter()
+            .flat_map(move |(&(tile_x, tile_y), &index)| {
+                let tile = &self.tiles[index];
+                (0..TILE_SIZE).flat_map(move |y| {
+                    let row = tile[y as usize];
+                    (0..TILE_SIZE)
+                        .filter(move |x| row & (1 << x) != 0)
+                        .map(move |x| (tile_x * TILE_SIZE + x, tile_y * TILE_SIZE + y))
+                })
+            })
+    }
+}
+
+/// Splits a position into the coordinates of its tile and the offset within the tile.
+fn split((x, y): (i32, i32)) -> ((i32, i32), (usize, usize)) {
+    let tile = (x.div_euclid(TILE_SIZE), y.div_euclid(TILE_SIZE));
+    let offset = (
+        x.rem_euclid(TILE_SIZE) as usize,
+        y.rem_euclid(TILE_SIZE) as usize,
+    );
+    (tile, offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{TileMap, Visited};
+
+    #[test]
+    fn same_positions_as_hash_set() {
+        let mut tile_map = TileMap::new();
+        let mut hash_set = HashSet::new();
+        // Walk a spiral crossing several tiles and all four quadrants, revisiting positions
+        let mut pos = (0, 0);
+        for length in 1..200 {
+            let dir = [(1, 0), (0, 1), (-1, 0), (0, -1)][length % 4];
+            for _ in 0..length % 150 {
+                pos = (pos.0 + dir.0, pos.1 + dir.1);
+                tile_map.insert(pos);
+                hash_set.insert(pos);
+            }
+        }
+
+        assert_eq!(hash_set.len(), tile_map.len());
+        let mut positions: Vec<_> = tile_map.positions().collect();
+        positions.sort_unstable();
+        let mut expected: Vec<_> = hash_set.iter().copied().collect();
+        expected.sort_unstable();
+        assert_eq!(expected, positions);
+        assert_eq!(hash_set.contains(&(-64, -1)), tile_map.contains((-64, -1)));
+        assert!(!tile_map.contains((10_000, 10_000)));
+    }
+}